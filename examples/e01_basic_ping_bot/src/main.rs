@@ -55,6 +55,12 @@ impl EventHandler for Handler {
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    if args.next().as_deref() == Some("reference") {
+        serenity_commands::reference::run::<Command>(args)?;
+        return Ok(());
+    }
+
     let token = std::env::var("DISCORD_TOKEN")?;
     let application_id = std::env::var("APPLICATION_ID")?;
     let application_id = application_id.parse::<u64>()?;
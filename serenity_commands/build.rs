@@ -0,0 +1,19 @@
+use std::env;
+
+// Sets `cfg(serenity = "...")` to the version of Serenity in use, or
+// `cfg(twilight)` when Twilight is used. Enabling several of them is an error
+// raised by the crate itself, so the oldest one is picked here meanwhile.
+fn main() {
+    println!("cargo::rustc-check-cfg=cfg(serenity, values(\"0_10\", \"0_11\", \"0_12\"))");
+    println!("cargo::rustc-check-cfg=cfg(twilight)");
+
+    let version = ["0_10", "0_11", "0_12"]
+        .into_iter()
+        .find(|v| env::var_os(format!("CARGO_FEATURE_SERENITY_{}", v)).is_some());
+
+    if let Some(version) = version {
+        println!("cargo::rustc-cfg=serenity=\"{}\"", version);
+    } else if env::var_os("CARGO_FEATURE_TWILIGHT").is_some() {
+        println!("cargo::rustc-cfg=twilight");
+    }
+}
@@ -0,0 +1,36 @@
+//! Encoding of the fields of components in their custom ids.
+//!
+//! Components deriving `Component` store their fields in the custom id, after
+//! the id of the component and separated by [`SEPARATOR`]. The fields are
+//! escaped, so that they can contain the separator themselves.
+
+use crate::error::ParseError;
+
+/// The separator between the id of a component and its fields.
+pub const SEPARATOR: char = ':';
+
+const ESCAPED_SEPARATOR: &str = "%3A";
+
+/// The maximum number of characters in a custom id, as set by Discord.
+pub const MAX_CUSTOM_ID_LENGTH: usize = 100;
+
+/// Escapes `field` so that it does not contain [`SEPARATOR`].
+pub fn escape(field: &str) -> String {
+    field.replace('%', "%25").replace(SEPARATOR, ESCAPED_SEPARATOR)
+}
+
+/// Reverses [`escape`].
+pub fn unescape(field: &str) -> String {
+    // Every `%` of an escaped field starts an escape sequence, so the
+    // separators can be restored first.
+    field.replace(ESCAPED_SEPARATOR, &SEPARATOR.to_string()).replace("%25", "%")
+}
+
+/// Checks that `custom_id` fits in the length allowed by Discord.
+pub fn check_length(custom_id: String) -> Result<String, ParseError> {
+    if custom_id.chars().count() > MAX_CUSTOM_ID_LENGTH {
+        return Err(ParseError::InvalidCustomId(custom_id));
+    }
+
+    Ok(custom_id)
+}
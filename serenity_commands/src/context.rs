@@ -0,0 +1,359 @@
+//! Responding to the interaction a command was parsed out of.
+//!
+//! [`CommandContext`] holds the context, the interaction and the parsed
+//! command, and sends the common responses without builders:
+//!
+//! ```ignore
+//! let ctx = CommandContext::parse::<AllCommands>(&ctx, &interaction)?;
+//!
+//! match &ctx.command {
+//!     AllCommands::Ping(_) => ctx.reply("pong").await?,
+//!     // ...
+//! }
+//! ```
+//!
+//! The context keeps track of the response, so replies after a deferral edit
+//! the deferred response, replies after the first one are sent as followups,
+//! and followups and edits made before any response defer it first.
+//!
+//! Functions defined with `#[serenity_commands::command]` can take a
+//! `CommandContext` in place of the context and the interaction.
+
+use std::sync::Arc;
+
+use serenity::builder::CreateEmbed;
+#[cfg(serenity = "0_12")]
+use serenity::builder::{
+    CreateInteractionResponse,
+    CreateInteractionResponseFollowup,
+    CreateInteractionResponseMessage,
+    EditInteractionResponse,
+};
+use serenity::client::Context;
+#[cfg(serenity = "0_12")]
+use serenity::model::application::CommandInteraction;
+use serenity::model::channel::Message;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::model::interactions::application_command::ApplicationCommandInteraction as CommandInteraction;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::model::interactions::{
+    InteractionApplicationCommandCallbackDataFlags,
+    InteractionResponseType,
+};
+use serenity::Result;
+use tokio::sync::Mutex;
+
+use crate::error::ParseError;
+use crate::Commands;
+
+/// A parsed command, along with the context and the interaction it was parsed
+/// out of.
+pub struct CommandContext<'a, C = ()> {
+    pub ctx: &'a Context,
+    pub interaction: &'a CommandInteraction,
+    pub command: C,
+    state: Arc<Mutex<ResponseState>>,
+}
+
+/// How far the response to an interaction has gone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResponseState {
+    Pending,
+    Deferred,
+    Responded,
+}
+
+impl<'a> CommandContext<'a> {
+    /// Parses `interaction` into one of the commands of `C`.
+    pub fn parse<C: Commands>(
+        ctx: &'a Context,
+        interaction: &'a CommandInteraction,
+    ) -> std::result::Result<CommandContext<'a, C>, ParseError> {
+        let command = C::parse(interaction.clone())?;
+
+        Ok(CommandContext::new(ctx, interaction, command))
+    }
+}
+
+impl<'a, C> CommandContext<'a, C> {
+    pub fn new(ctx: &'a Context, interaction: &'a CommandInteraction, command: C) -> Self {
+        Self {
+            ctx,
+            interaction,
+            command,
+            state: Arc::new(Mutex::new(ResponseState::Pending)),
+        }
+    }
+
+    /// Returns a context without the command, which tracks the same response.
+    pub(crate) fn share(&self) -> CommandContext<'a> {
+        CommandContext {
+            ctx: self.ctx,
+            interaction: self.interaction,
+            command: (),
+            state: Arc::clone(&self.state),
+        }
+    }
+
+    /// Responds with a message.
+    pub async fn reply(&self, content: impl ToString) -> Result<()> {
+        self.respond(Response {
+            content: Some(content.to_string()),
+            embed: None,
+            ephemeral: false,
+        })
+        .await
+    }
+
+    /// Responds with a message only the invoking user can see.
+    ///
+    /// After a deferral, the deferred response keeps the visibility it was
+    /// deferred with.
+    pub async fn reply_ephemeral(&self, content: impl ToString) -> Result<()> {
+        self.respond(Response {
+            content: Some(content.to_string()),
+            embed: None,
+            ephemeral: true,
+        })
+        .await
+    }
+
+    /// Responds with a message holding `embed`.
+    pub async fn reply_embed(&self, embed: CreateEmbed) -> Result<()> {
+        self.respond(Response {
+            content: None,
+            embed: Some(embed),
+            ephemeral: false,
+        })
+        .await
+    }
+
+    /// Acknowledges the interaction, showing that the bot is thinking until
+    /// the next reply. Does nothing if the interaction was already responded
+    /// to.
+    pub async fn defer(&self) -> Result<()> {
+        self.defer_if_pending(false).await
+    }
+
+    /// Like [`Self::defer`], but only the invoking user can see the response.
+    pub async fn defer_ephemeral(&self) -> Result<()> {
+        self.defer_if_pending(true).await
+    }
+
+    /// Defers the response, unless the interaction was already responded to,
+    /// waiting for a response being sent.
+    pub(crate) async fn defer_if_pending(&self, ephemeral: bool) -> Result<()> {
+        let mut state = self.state.lock().await;
+
+        if *state == ResponseState::Pending {
+            self.create_deferral(ephemeral).await?;
+            *state = ResponseState::Deferred;
+        }
+
+        Ok(())
+    }
+
+    async fn respond(&self, response: Response) -> Result<()> {
+        let mut state = self.state.lock().await;
+
+        match *state {
+            ResponseState::Pending => self.create_response(response).await?,
+            ResponseState::Deferred => {
+                self.edit_response(response).await?;
+            },
+            ResponseState::Responded => {
+                self.create_followup(response).await?;
+            },
+        }
+
+        *state = ResponseState::Responded;
+
+        Ok(())
+    }
+
+    /// Sends a message after the response. If the interaction was not
+    /// responded to, it is deferred first, as Discord only accepts followups
+    /// once it was.
+    pub async fn followup(&self, content: impl ToString) -> Result<Message> {
+        let mut state = self.state.lock().await;
+
+        if *state == ResponseState::Pending {
+            self.create_deferral(false).await?;
+            *state = ResponseState::Deferred;
+        }
+
+        let message = self
+            .create_followup(Response {
+                content: Some(content.to_string()),
+                embed: None,
+                ephemeral: false,
+            })
+            .await?;
+
+        *state = ResponseState::Responded;
+
+        Ok(message)
+    }
+
+    /// Edits the response, or the deferred one. If the interaction was not
+    /// responded to, it is deferred first, so that there is a response to
+    /// edit.
+    pub async fn edit_original(&self, content: impl ToString) -> Result<Message> {
+        let mut state = self.state.lock().await;
+
+        if *state == ResponseState::Pending {
+            self.create_deferral(false).await?;
+            *state = ResponseState::Deferred;
+        }
+
+        let message = self
+            .edit_response(Response {
+                content: Some(content.to_string()),
+                embed: None,
+                ephemeral: false,
+            })
+            .await?;
+
+        *state = ResponseState::Responded;
+
+        Ok(message)
+    }
+}
+
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+impl<'a, C> CommandContext<'a, C> {
+    async fn create_deferral(&self, ephemeral: bool) -> Result<()> {
+        self.interaction
+            .create_interaction_response(&self.ctx.http, |r| {
+                r.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+                    .interaction_response_data(|data| {
+                        if ephemeral {
+                            data.flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL);
+                        }
+
+                        data
+                    })
+            })
+            .await
+    }
+
+    async fn create_response(&self, response: Response) -> Result<()> {
+        self.interaction
+            .create_interaction_response(&self.ctx.http, |r| {
+                r.kind(InteractionResponseType::ChannelMessageWithSource).interaction_response_data(
+                    |data| {
+                        if let Some(content) = response.content {
+                            data.content(content);
+                        }
+
+                        if let Some(embed) = response.embed {
+                            data.add_embed(embed);
+                        }
+
+                        if response.ephemeral {
+                            data.flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL);
+                        }
+
+                        data
+                    },
+                )
+            })
+            .await
+    }
+
+    async fn edit_response(&self, response: Response) -> Result<Message> {
+        self.interaction
+            .edit_original_interaction_response(&self.ctx.http, |r| {
+                if let Some(content) = response.content {
+                    r.content(content);
+                }
+
+                if let Some(embed) = response.embed {
+                    r.add_embed(embed);
+                }
+
+                r
+            })
+            .await
+    }
+
+    async fn create_followup(&self, response: Response) -> Result<Message> {
+        self.interaction
+            .create_followup_message(&self.ctx.http, |f| {
+                if let Some(content) = response.content {
+                    f.content(content);
+                }
+
+                if let Some(embed) = response.embed {
+                    f.add_embed(embed);
+                }
+
+                if response.ephemeral {
+                    f.flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL);
+                }
+
+                f
+            })
+            .await
+    }
+}
+
+#[cfg(serenity = "0_12")]
+impl<'a, C> CommandContext<'a, C> {
+    async fn create_deferral(&self, ephemeral: bool) -> Result<()> {
+        let message = CreateInteractionResponseMessage::new().ephemeral(ephemeral);
+
+        self.interaction.create_response(self.ctx, CreateInteractionResponse::Defer(message)).await
+    }
+
+    async fn create_response(&self, response: Response) -> Result<()> {
+        let mut message = CreateInteractionResponseMessage::new().ephemeral(response.ephemeral);
+
+        if let Some(content) = response.content {
+            message = message.content(content);
+        }
+
+        if let Some(embed) = response.embed {
+            message = message.embed(embed);
+        }
+
+        self.interaction
+            .create_response(self.ctx, CreateInteractionResponse::Message(message))
+            .await
+    }
+
+    async fn edit_response(&self, response: Response) -> Result<Message> {
+        let mut edit = EditInteractionResponse::new();
+
+        if let Some(content) = response.content {
+            edit = edit.content(content);
+        }
+
+        if let Some(embed) = response.embed {
+            edit = edit.embed(embed);
+        }
+
+        self.interaction.edit_response(self.ctx, edit).await
+    }
+
+    async fn create_followup(&self, response: Response) -> Result<Message> {
+        let mut followup = CreateInteractionResponseFollowup::new().ephemeral(response.ephemeral);
+
+        if let Some(content) = response.content {
+            followup = followup.content(content);
+        }
+
+        if let Some(embed) = response.embed {
+            followup = followup.embed(embed);
+        }
+
+        self.interaction.create_followup(self.ctx, followup).await
+    }
+}
+
+/// The contents of a response to an interaction.
+struct Response {
+    content: Option<String>,
+    embed: Option<CreateEmbed>,
+    ephemeral: bool,
+}
@@ -0,0 +1,267 @@
+//! Running parsed commands.
+//!
+//! Commands defined with `#[serenity_commands::command]` implement
+//! [`Handler`] by calling their function, and enums deriving `Commands` with
+//! `#[commands(dispatch)]` implement it by running the parsed command. The
+//! functions are not collected into such an enum for you: the structs they
+//! are turned into, named after them in Pascal case, are listed in one like
+//! derived commands.
+//!
+//! Interactions must be responded to within 3 seconds. Slow commands taking a
+//! [`CommandContext`] can be deferred with `#[command(defer)]`, or a
+//! [`Dispatcher`] can defer any command that has not responded within a
+//! threshold.
+//!
+//! A [`Dispatcher`] also runs [`Middleware`] around every command, for
+//! logging, metrics, auditing or checks that stop commands from running.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serenity::async_trait;
+use serenity::client::Context;
+use serenity::futures::future;
+#[cfg(serenity = "0_12")]
+use serenity::model::application::CommandInteraction;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::model::interactions::application_command::ApplicationCommandInteraction as CommandInteraction;
+
+use crate::context::CommandContext;
+use crate::error::ParseError;
+use crate::info::{self, CommandMeta};
+use crate::Commands;
+
+/// A command that can be run once parsed.
+#[async_trait]
+pub trait Handler: Sized + Send {
+    type Output;
+
+    async fn run(self, ctx: &Context, interaction: &CommandInteraction) -> Self::Output;
+
+    /// Runs the command with a context tracking the response to the
+    /// interaction.
+    async fn run_with_context(self, context: CommandContext<'_>) -> Self::Output {
+        self.run(context.ctx, context.interaction).await
+    }
+}
+
+/// Hooks run around every command dispatched by a [`Dispatcher`], where `O` is
+/// the output of the commands.
+///
+/// The hooks run in the order the middleware was added before the command,
+/// and in the reverse order after it.
+#[async_trait]
+pub trait Middleware<O>: Send + Sync {
+    /// Runs before the command. Returning an output stops the command and the
+    /// remaining middleware from running, and the output is dispatched in its
+    /// place.
+    async fn before(&self, _invocation: &Invocation<'_>) -> Option<O> {
+        None
+    }
+
+    /// Runs after the command with its output, and how long it ran for.
+    ///
+    /// Only runs if [`Self::before`] let the command run. If a later
+    /// middleware stopped it, the output is that middleware's, and the
+    /// elapsed time is zero.
+    async fn after(&self, _invocation: &Invocation<'_>, _output: &O, _elapsed: Duration) {}
+}
+
+/// A command about to be run by a [`Dispatcher`].
+pub struct Invocation<'a> {
+    /// The space-separated names of the command and its subcommands, such as
+    /// `config prefix set`.
+    pub path: String,
+    /// The metadata of the invoked command or subcommand.
+    pub meta: Option<&'static CommandMeta>,
+    /// A context tracking the same response as the command.
+    pub context: CommandContext<'a>,
+}
+
+/// Parses interactions and runs the parsed commands.
+pub struct Dispatcher<O = ()> {
+    auto_defer: Option<Duration>,
+    ephemeral: bool,
+    middleware: Vec<Arc<dyn Middleware<O>>>,
+}
+
+impl<O> Dispatcher<O> {
+    pub fn new() -> Self {
+        Self {
+            auto_defer: None,
+            ephemeral: false,
+            middleware: Vec::new(),
+        }
+    }
+
+    /// Defers the response when the command has not responded within
+    /// `threshold`. Replies through the [`CommandContext`] then edit the
+    /// deferred response.
+    ///
+    /// Commands must respond through their [`CommandContext`], which is the
+    /// only one to know whether the response was deferred. Responding through
+    /// the interaction fails once it is.
+    pub fn auto_defer(mut self, threshold: Duration) -> Self {
+        self.auto_defer = Some(threshold);
+        self
+    }
+
+    /// Makes automatically deferred responses visible only to the invoking
+    /// user.
+    pub fn ephemeral(mut self, ephemeral: bool) -> Self {
+        self.ephemeral = ephemeral;
+        self
+    }
+
+    /// Adds `middleware` to run around the commands, inside the middleware
+    /// added before it.
+    pub fn middleware(mut self, middleware: impl Middleware<O> + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Parses `interaction` into one of the commands of `C`, and runs it.
+    pub async fn dispatch<C>(
+        &self,
+        ctx: &Context,
+        interaction: &CommandInteraction,
+    ) -> Result<O, ParseError>
+    where
+        C: Commands + Handler<Output = O>,
+    {
+        let dispatch = self.dispatch_command::<C>(ctx, interaction);
+
+        #[cfg(feature = "tracing")]
+        let dispatch =
+            tracing::Instrument::instrument(dispatch, crate::trace::dispatch_span(interaction));
+
+        dispatch.await
+    }
+
+    async fn dispatch_command<C>(
+        &self,
+        ctx: &Context,
+        interaction: &CommandInteraction,
+    ) -> Result<O, ParseError>
+    where
+        C: Commands + Handler<Output = O>,
+    {
+        let command = C::parse(interaction.clone());
+
+        #[cfg(feature = "metrics")]
+        if let Err(e) = &command {
+            crate::metrics::parse_failed(e);
+        }
+
+        let command = command?;
+        let context = CommandContext::new(ctx, interaction, ());
+        let path = info::path(&interaction.data);
+
+        #[cfg(feature = "metrics")]
+        crate::metrics::invoked(&path);
+
+        let invocation = Invocation {
+            meta: CommandMeta::find(C::METADATA, &path),
+            path,
+            context: context.share(),
+        };
+
+        let mut entered = 0;
+        let mut stopped = None;
+
+        for middleware in &self.middleware {
+            if let Some(output) = middleware.before(&invocation).await {
+                stopped = Some(output);
+                break;
+            }
+
+            entered += 1;
+        }
+
+        let (output, elapsed) = match stopped {
+            Some(output) => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!("middleware stopped the command");
+                #[cfg(feature = "metrics")]
+                crate::metrics::stopped(&invocation.path);
+
+                (output, Duration::ZERO)
+            },
+            None => {
+                let start = Instant::now();
+                let output = self.run(command, context).await;
+                let elapsed = start.elapsed();
+
+                #[cfg(feature = "tracing")]
+                tracing::debug!(?elapsed, "ran command");
+                #[cfg(feature = "metrics")]
+                crate::metrics::ran(&invocation.path, elapsed);
+
+                (output, elapsed)
+            },
+        };
+
+        for middleware in self.middleware[..entered].iter().rev() {
+            middleware.after(&invocation, &output, elapsed).await;
+        }
+
+        Ok(output)
+    }
+
+    async fn run<C>(&self, command: C, context: CommandContext<'_>) -> O
+    where
+        C: Handler<Output = O>,
+    {
+        let threshold = match self.auto_defer {
+            Some(threshold) => threshold,
+            None => return command.run_with_context(context).await,
+        };
+
+        let deferral = context.share();
+        let mut run = command.run_with_context(context);
+
+        if let Ok(output) = tokio::time::timeout(threshold, &mut run).await {
+            return output;
+        }
+
+        // The command keeps running while the deferral waits for a response
+        // it may be sending. If the deferral fails, the next reply still
+        // creates the response.
+        let (_deferred, output) =
+            future::join(deferral.defer_if_pending(self.ephemeral), run).await;
+
+        #[cfg(feature = "tracing")]
+        if let Err(e) = &_deferred {
+            tracing::warn!(error = %e, "failed to defer the response");
+        }
+
+        output
+    }
+}
+
+impl<O> Default for Dispatcher<O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<O> Clone for Dispatcher<O> {
+    fn clone(&self) -> Self {
+        Self {
+            auto_defer: self.auto_defer,
+            ephemeral: self.ephemeral,
+            middleware: self.middleware.clone(),
+        }
+    }
+}
+
+/// Parses `interaction` into one of the commands of `C`, and runs it.
+pub async fn dispatch<C>(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+) -> Result<C::Output, ParseError>
+where
+    C: Commands + Handler,
+{
+    Dispatcher::new().dispatch::<C>(ctx, interaction).await
+}
@@ -0,0 +1,224 @@
+//! An endpoint for receiving interactions over HTTP rather than the gateway.
+//!
+//! Discord signs every request it sends to an interactions endpoint with the
+//! application's key pair. Requests whose signature does not match the
+//! application's public key are rejected, as Discord requires.
+//!
+//! Modal submissions are passed to a separate handler, given with
+//! [`router_with_modals`].
+
+use std::error::Error as StdError;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::Deserialize;
+use serde_json::{json, Value};
+#[cfg(not(serenity = "0_11"))]
+use serenity::builder::CreateInteractionResponse;
+#[cfg(serenity = "0_11")]
+use serenity::json::hashmap_to_json_map;
+#[cfg(serenity = "0_12")]
+use serenity::model::application::CommandInteraction;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::model::interactions::application_command::ApplicationCommandInteraction as CommandInteraction;
+#[cfg(serenity = "0_10")]
+use serenity::utils::hashmap_to_json_map;
+
+use crate::modal::ModalSubmit;
+use crate::Commands;
+
+// The response owns its attachments, if any, so that it can be returned by
+// the handler.
+#[cfg(serenity = "0_11")]
+type CreateInteractionResponse = serenity::builder::CreateInteractionResponse<'static>;
+
+const SIGNATURE_HEADER: &str = "X-Signature-Ed25519";
+const TIMESTAMP_HEADER: &str = "X-Signature-Timestamp";
+
+const PING: u64 = 1;
+const APPLICATION_COMMAND: u64 = 2;
+const MODAL_SUBMIT: u64 = 5;
+
+// The type of the modal handler of routers without one.
+type NoModals = fn(ModalSubmit, Value) -> std::future::Ready<CreateInteractionResponse>;
+
+#[derive(Debug, Clone)]
+pub struct InvalidPublicKey;
+
+impl fmt::Display for InvalidPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid public key, expected 32 hex-encoded bytes")
+    }
+}
+
+impl StdError for InvalidPublicKey {}
+
+/// The public key of an application, as shown in the developer portal.
+#[derive(Debug, Clone, Copy)]
+pub struct PublicKey(VerifyingKey);
+
+impl PublicKey {
+    pub fn from_hex(key: &str) -> Result<Self, InvalidPublicKey> {
+        let mut bytes = [0; 32];
+        hex::decode_to_slice(key, &mut bytes).map_err(|_| InvalidPublicKey)?;
+
+        VerifyingKey::from_bytes(&bytes).map(Self).map_err(|_| InvalidPublicKey)
+    }
+
+    /// Checks that `signature` is the hex-encoded signature of the timestamp
+    /// followed by the body of the request.
+    pub fn verify(&self, signature: &str, timestamp: &str, body: &[u8]) -> bool {
+        let mut bytes = [0; 64];
+        if hex::decode_to_slice(signature, &mut bytes).is_err() {
+            return false;
+        }
+
+        let message = [timestamp.as_bytes(), body].concat();
+
+        self.0.verify(&message, &Signature::from_bytes(&bytes)).is_ok()
+    }
+}
+
+struct Endpoint<H, M> {
+    key: PublicKey,
+    handler: H,
+    modal_handler: Option<M>,
+}
+
+/// Creates a router accepting interactions at its root.
+///
+/// Pings are answered automatically. Application commands are parsed into
+/// `C` and passed to `handler` alongside the interaction, and the response
+/// it builds is sent back to Discord. Modal submissions are rejected.
+pub fn router<C, H, Fut>(key: PublicKey, handler: H) -> Router
+where
+    C: Commands + Send + 'static,
+    H: Fn(C, CommandInteraction) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = CreateInteractionResponse> + Send + 'static,
+{
+    new_router::<C, H, Fut, NoModals, _>(key, handler, None)
+}
+
+/// Like [`router`], but also passes modal submissions to `modal_handler`,
+/// alongside the payload of the interaction.
+///
+/// The submissions can be parsed with the `parse_submit` function generated
+/// by `#[derive(Modal)]`.
+pub fn router_with_modals<C, H, Fut, M, MFut>(
+    key: PublicKey,
+    handler: H,
+    modal_handler: M,
+) -> Router
+where
+    C: Commands + Send + 'static,
+    H: Fn(C, CommandInteraction) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = CreateInteractionResponse> + Send + 'static,
+    M: Fn(ModalSubmit, Value) -> MFut + Send + Sync + 'static,
+    MFut: Future<Output = CreateInteractionResponse> + Send + 'static,
+{
+    new_router::<C, H, Fut, M, MFut>(key, handler, Some(modal_handler))
+}
+
+fn new_router<C, H, Fut, M, MFut>(key: PublicKey, handler: H, modal_handler: Option<M>) -> Router
+where
+    C: Commands + Send + 'static,
+    H: Fn(C, CommandInteraction) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = CreateInteractionResponse> + Send + 'static,
+    M: Fn(ModalSubmit, Value) -> MFut + Send + Sync + 'static,
+    MFut: Future<Output = CreateInteractionResponse> + Send + 'static,
+{
+    let endpoint = Arc::new(Endpoint {
+        key,
+        handler,
+        modal_handler,
+    });
+
+    Router::new().route("/", post(handle::<C, H, Fut, M, MFut>)).with_state(endpoint)
+}
+
+async fn handle<C, H, Fut, M, MFut>(
+    State(endpoint): State<Arc<Endpoint<H, M>>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response
+where
+    C: Commands + Send + 'static,
+    H: Fn(C, CommandInteraction) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = CreateInteractionResponse> + Send + 'static,
+    M: Fn(ModalSubmit, Value) -> MFut + Send + Sync + 'static,
+    MFut: Future<Output = CreateInteractionResponse> + Send + 'static,
+{
+    let signature = headers.get(SIGNATURE_HEADER).and_then(|v| v.to_str().ok());
+    let timestamp = headers.get(TIMESTAMP_HEADER).and_then(|v| v.to_str().ok());
+
+    let verified = match (signature, timestamp) {
+        (Some(signature), Some(timestamp)) => endpoint.key.verify(signature, timestamp, &body),
+        _ => false,
+    };
+
+    if !verified {
+        return (StatusCode::UNAUTHORIZED, "invalid request signature").into_response();
+    }
+
+    let value = match serde_json::from_slice::<Value>(&body) {
+        Ok(value) => value,
+        Err(_) => return (StatusCode::BAD_REQUEST, "invalid interaction payload").into_response(),
+    };
+
+    match value.get("type").and_then(Value::as_u64) {
+        Some(PING) => Json(json!({ "type": PING })).into_response(),
+        Some(APPLICATION_COMMAND) => {
+            let interaction = match <CommandInteraction as Deserialize>::deserialize(&value) {
+                Ok(interaction) => interaction,
+                Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+            };
+
+            let command = match C::parse(interaction.clone()) {
+                Ok(command) => command,
+                Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+            };
+
+            let response = (endpoint.handler)(command, interaction).await;
+
+            respond(response)
+        },
+        Some(MODAL_SUBMIT) => {
+            let modal_handler = match &endpoint.modal_handler {
+                Some(modal_handler) => modal_handler,
+                None => return unsupported(),
+            };
+
+            let submit = match ModalSubmit::from_json(&value) {
+                Ok(submit) => submit,
+                Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+            };
+
+            let response = modal_handler(submit, value).await;
+
+            respond(response)
+        },
+        _ => unsupported(),
+    }
+}
+
+fn unsupported() -> Response {
+    (StatusCode::BAD_REQUEST, "unsupported interaction type").into_response()
+}
+
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+fn respond(response: CreateInteractionResponse) -> Response {
+    Json(Value::Object(hashmap_to_json_map(response.0))).into_response()
+}
+
+#[cfg(serenity = "0_12")]
+fn respond(response: CreateInteractionResponse) -> Response {
+    Json(response).into_response()
+}
@@ -1,16 +1,56 @@
 use std::error::Error as StdError;
 use std::fmt;
 
-use serenity::model::interactions::application_command::ApplicationCommandOptionType;
+#[cfg(serenity = "0_12")]
+use serenity::model::application::CommandOptionType;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::model::interactions::application_command::ApplicationCommandOptionType as CommandOptionType;
+#[cfg(twilight)]
+use twilight_model::application::command::CommandOptionType;
 
 #[derive(Debug, Clone)]
 pub enum ParseError {
-    InvalidType(ApplicationCommandOptionType),
+    InvalidType(CommandOptionType),
     UnknownCommand(String),
     UnknownSubCommand(String),
     UnknownSubCommandGroup(String),
     UnknownOption(String),
     MissingOption(&'static str),
+    InvalidPayload(String),
+    UnknownComponent(String),
+    InvalidCustomId(String),
+    UnknownModal(String),
+    MissingInput(&'static str),
+    InvalidInput(&'static str),
+    MissingName,
+    InvalidArgument(&'static str),
+    UnexpectedArgument(String),
+    UnclosedQuote,
+}
+
+impl ParseError {
+    /// Returns the name of the variant in snake case, such as
+    /// `unknown_command`, to tell errors apart without their details.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::InvalidType(_) => "invalid_type",
+            Self::UnknownCommand(_) => "unknown_command",
+            Self::UnknownSubCommand(_) => "unknown_subcommand",
+            Self::UnknownSubCommandGroup(_) => "unknown_subcommand_group",
+            Self::UnknownOption(_) => "unknown_option",
+            Self::MissingOption(_) => "missing_option",
+            Self::InvalidPayload(_) => "invalid_payload",
+            Self::UnknownComponent(_) => "unknown_component",
+            Self::InvalidCustomId(_) => "invalid_custom_id",
+            Self::UnknownModal(_) => "unknown_modal",
+            Self::MissingInput(_) => "missing_input",
+            Self::InvalidInput(_) => "invalid_input",
+            Self::MissingName => "missing_name",
+            Self::InvalidArgument(_) => "invalid_argument",
+            Self::UnexpectedArgument(_) => "unexpected_argument",
+            Self::UnclosedQuote => "unclosed_quote",
+        }
+    }
 }
 
 impl fmt::Display for ParseError {
@@ -22,6 +62,16 @@ impl fmt::Display for ParseError {
             Self::UnknownSubCommandGroup(cmd) => write!(f, "unknown subcommand group \"{}\"", cmd),
             Self::UnknownOption(opt) => write!(f, "unknown option \"{}\"", opt),
             Self::MissingOption(opt) => write!(f, "missing option \"{}\"", opt),
+            Self::InvalidPayload(err) => write!(f, "invalid interaction payload: {}", err),
+            Self::UnknownComponent(id) => write!(f, "unknown component \"{}\"", id),
+            Self::InvalidCustomId(id) => write!(f, "invalid custom id \"{}\"", id),
+            Self::UnknownModal(id) => write!(f, "unknown modal \"{}\"", id),
+            Self::MissingInput(input) => write!(f, "missing input \"{}\"", input),
+            Self::InvalidInput(input) => write!(f, "invalid value for input \"{}\"", input),
+            Self::MissingName => write!(f, "missing command or subcommand name"),
+            Self::InvalidArgument(opt) => write!(f, "invalid argument for option \"{}\"", opt),
+            Self::UnexpectedArgument(arg) => write!(f, "unexpected argument \"{}\"", arg),
+            Self::UnclosedQuote => write!(f, "unclosed quote"),
         }
     }
 }
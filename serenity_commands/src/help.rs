@@ -0,0 +1,295 @@
+//! A ready-to-use `/help` command, rendering the [`CommandMeta`] of a set of
+//! commands as an embed.
+//!
+//! Add [`Help`] as a variant of an enum deriving `Commands`, and respond to
+//! it with `Help::create_response`, which returns the response on Serenity
+//! 0.12:
+//!
+//! ```ignore
+//! match AllCommands::parse(interaction.clone())? {
+//!     AllCommands::Help(help) => {
+//!         interaction
+//!             .create_interaction_response(&ctx, |r| help.create_response::<AllCommands>(r))
+//!             .await?;
+//!     },
+//!     // ...
+//! }
+//! ```
+//!
+//! With Twilight, only the embed is created, by `Help::create_embed`.
+
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::builder::CreateEmbed;
+#[cfg(serenity = "0_12")]
+use serenity::builder::{
+    CreateEmbed,
+    CreateEmbedFooter,
+    CreateInteractionResponse,
+    CreateInteractionResponseMessage,
+};
+#[cfg(twilight)]
+use twilight_model::channel::message::embed::{Embed, EmbedField, EmbedFooter};
+
+use crate::info::CommandMeta;
+use crate::macros::Command;
+use crate::Commands;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use crate::CreateInteractionResponse;
+
+/// The number of commands listed on each page.
+pub const PAGE_SIZE: usize = 10;
+
+/// The maximum number of characters in the value of an embed field.
+const FIELD_VALUE_LIMIT: usize = 1024;
+
+/// Show the available commands.
+#[derive(Debug, Clone, PartialEq, Command)]
+#[command(name = "help", prefix)]
+pub struct Help {
+    /// The command to describe, such as `config prefix set`.
+    #[option(string)]
+    pub command: Option<String>,
+    /// The page of commands to show.
+    #[option(integer)]
+    pub page: Option<i64>,
+}
+
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+impl Help {
+    /// Renders the help for the commands of `C` into `embed`.
+    pub fn create_embed<'a, C: Commands>(&self, embed: &'a mut CreateEmbed) -> &'a mut CreateEmbed {
+        self.render(C::METADATA, embed)
+    }
+
+    /// Sets `response` to show the help for the commands of `C`.
+    pub fn create_response<'a, 'b, C: Commands>(
+        &self,
+        response: &'a mut CreateInteractionResponse<'b>,
+    ) -> &'a mut CreateInteractionResponse<'b> {
+        let mut embed = CreateEmbed::default();
+        self.create_embed::<C>(&mut embed);
+
+        response.interaction_response_data(|data| data.add_embed(embed))
+    }
+
+    /// Renders either the details of the requested command, or the requested
+    /// page of `commands`.
+    pub fn render<'a>(
+        &self,
+        commands: &[&'static CommandMeta],
+        embed: &'a mut CreateEmbed,
+    ) -> &'a mut CreateEmbed {
+        let rendered = self.contents(commands);
+
+        embed.title(rendered.title).description(rendered.description);
+
+        for (name, value) in rendered.fields {
+            embed.field(name, value, false);
+        }
+
+        if let Some(footer) = rendered.footer {
+            embed.footer(|f| f.text(footer));
+        }
+
+        embed
+    }
+}
+
+#[cfg(serenity = "0_12")]
+impl Help {
+    /// Renders the help for the commands of `C`.
+    pub fn create_embed<C: Commands>(&self) -> CreateEmbed {
+        self.render(C::METADATA)
+    }
+
+    /// Creates a response showing the help for the commands of `C`.
+    pub fn create_response<C: Commands>(&self) -> CreateInteractionResponse {
+        let message = CreateInteractionResponseMessage::new().embed(self.create_embed::<C>());
+
+        CreateInteractionResponse::Message(message)
+    }
+
+    /// Renders either the details of the requested command, or the requested
+    /// page of `commands`.
+    pub fn render(&self, commands: &[&'static CommandMeta]) -> CreateEmbed {
+        let rendered = self.contents(commands);
+
+        let mut embed = CreateEmbed::new().title(rendered.title).description(rendered.description);
+
+        for (name, value) in rendered.fields {
+            embed = embed.field(name, value, false);
+        }
+
+        if let Some(footer) = rendered.footer {
+            embed = embed.footer(CreateEmbedFooter::new(footer));
+        }
+
+        embed
+    }
+}
+
+#[cfg(twilight)]
+impl Help {
+    /// Renders the help for the commands of `C`.
+    pub fn create_embed<C: Commands>(&self) -> Embed {
+        self.render(C::METADATA)
+    }
+
+    /// Renders either the details of the requested command, or the requested
+    /// page of `commands`.
+    pub fn render(&self, commands: &[&'static CommandMeta]) -> Embed {
+        let rendered = self.contents(commands);
+
+        let fields = rendered
+            .fields
+            .into_iter()
+            .map(|(name, value)| EmbedField {
+                inline: false,
+                name: name.to_string(),
+                value,
+            })
+            .collect();
+
+        let footer = rendered.footer.map(|text| EmbedFooter {
+            icon_url: None,
+            proxy_icon_url: None,
+            text,
+        });
+
+        Embed {
+            author: None,
+            color: None,
+            description: Some(rendered.description),
+            fields,
+            footer,
+            image: None,
+            kind: "rich".to_string(),
+            provider: None,
+            thumbnail: None,
+            timestamp: None,
+            title: Some(rendered.title),
+            url: None,
+            video: None,
+        }
+    }
+}
+
+/// The contents of an embed, independent of the library.
+struct Contents {
+    title: String,
+    description: String,
+    fields: Vec<(&'static str, String)>,
+    footer: Option<String>,
+}
+
+impl Help {
+    fn contents(&self, commands: &[&'static CommandMeta]) -> Contents {
+        let path = match &self.command {
+            Some(path) => path.split_whitespace().collect::<Vec<_>>().join(" "),
+            None => return page(commands, self.page.unwrap_or(1)),
+        };
+
+        match CommandMeta::find(commands, &path) {
+            Some(info) => command(&path, info),
+            None => Contents {
+                title: "Unknown command".to_string(),
+                description: format!("There is no command named `/{}`.", path),
+                fields: Vec::new(),
+                footer: None,
+            },
+        }
+    }
+}
+
+/// Returns the number of pages needed to list `commands`.
+pub fn page_count(commands: &[&'static CommandMeta]) -> usize {
+    commands.len().div_ceil(PAGE_SIZE).max(1)
+}
+
+fn page(commands: &[&'static CommandMeta], page: i64) -> Contents {
+    let count = page_count(commands);
+    let page = page.clamp(1, count as i64) as usize;
+
+    let lines = commands
+        .iter()
+        .skip((page - 1) * PAGE_SIZE)
+        .take(PAGE_SIZE)
+        .map(|c| format!("`/{}` — {}", c.name, c.description))
+        .collect::<Vec<_>>();
+
+    let description =
+        if lines.is_empty() { "There are no commands.".to_string() } else { lines.join("\n") };
+
+    Contents {
+        title: "Commands".to_string(),
+        description,
+        fields: Vec::new(),
+        footer: Some(format!("Page {}/{}", page, count)),
+    }
+}
+
+fn command(path: &str, info: &CommandMeta) -> Contents {
+    let mut fields = info
+        .options()
+        .into_iter()
+        .map(|opt| {
+            let requirement = if opt.required { "required" } else { "optional" };
+
+            (opt.name, format!("{} ({}, {})", opt.description, opt.kind_name(), requirement))
+        })
+        .collect::<Vec<_>>();
+
+    if !info.subcommands.is_empty() {
+        let lines = info
+            .subcommands
+            .iter()
+            .map(|c| format!("`/{} {}` — {}", path, c.name, c.description))
+            .collect::<Vec<_>>();
+
+        for (i, value) in join_within(&lines, FIELD_VALUE_LIMIT).into_iter().enumerate() {
+            fields.push((if i == 0 { "Subcommands" } else { "Subcommands (continued)" }, value));
+        }
+    }
+
+    Contents {
+        title: format!("/{}", path),
+        description: info.description.to_string(),
+        fields,
+        footer: None,
+    }
+}
+
+/// Joins `lines` with newlines into as few values as possible, each at most
+/// `limit` characters long. Lines longer than `limit` are truncated.
+fn join_within(lines: &[String], limit: usize) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut value = String::new();
+    let mut len = 0;
+
+    for line in lines {
+        let line = match line.char_indices().nth(limit) {
+            Some((end, _)) => &line[..end],
+            None => line,
+        };
+        let line_len = line.chars().count();
+
+        if !value.is_empty() && len + 1 + line_len > limit {
+            values.push(std::mem::take(&mut value));
+            len = 0;
+        }
+
+        if !value.is_empty() {
+            value.push('\n');
+            len += 1;
+        }
+
+        value.push_str(line);
+        len += line_len;
+    }
+
+    if !value.is_empty() {
+        values.push(value);
+    }
+
+    values
+}
@@ -0,0 +1,173 @@
+//! Metadata about commands, generated by the derives from the names and
+//! descriptions known at compile time.
+
+#[cfg(serenity = "0_12")]
+use serenity::model::application::{CommandData, CommandDataOption, CommandDataOptionValue};
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::model::interactions::application_command::{
+    ApplicationCommandInteractionData as CommandData,
+    ApplicationCommandInteractionDataOption as CommandDataOption,
+    ApplicationCommandOptionType as CommandOptionType,
+};
+#[cfg(all(twilight, feature = "tracing"))]
+use twilight_model::application::interaction::application_command::{
+    CommandData,
+    CommandDataOption,
+    CommandOptionValue,
+};
+
+use crate::model::OptionKind;
+
+/// The structure of a command, subcommand group or subcommand, available at
+/// compile time through the `METADATA` constants of the traits.
+#[derive(Debug, Clone, Copy)]
+pub struct CommandMeta {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub options: &'static [OptionEntry],
+    pub subcommands: &'static [&'static CommandMeta],
+    /// The permissions members need to use the command, unless the server
+    /// changes them, as given with
+    /// `#[command(default_member_permissions = "...")]`. Discord only applies
+    /// them to top-level commands.
+    pub default_member_permissions: Option<PermissionsMeta>,
+    /// Whether the command can be used in direct messages, as given with
+    /// `#[command(dm_permission = ...)]`, or `None` to leave it to Discord,
+    /// which allows it.
+    pub dm_permission: Option<bool>,
+}
+
+/// A set of Discord permissions.
+#[derive(Debug, Clone, Copy)]
+pub struct PermissionsMeta {
+    /// The names of the permissions, such as `BAN_MEMBERS`.
+    pub names: &'static [&'static str],
+    /// The bits of the permissions, as sent to Discord.
+    pub bits: u64,
+}
+
+impl CommandMeta {
+    /// Finds a command or one of its subcommands by the space-separated path
+    /// of their names, such as `config prefix set`.
+    pub fn find(commands: &[&'static CommandMeta], path: &str) -> Option<&'static CommandMeta> {
+        let mut names = path.split_whitespace();
+
+        let name = names.next()?;
+        let mut meta = *commands.iter().find(|c| c.name == name)?;
+
+        for name in names {
+            meta = *meta.subcommands.iter().find(|c| c.name == name)?;
+        }
+
+        Some(meta)
+    }
+
+    /// Returns the options, with flattened sets replaced by their options.
+    pub fn options(&self) -> Vec<&'static OptionMeta> {
+        OptionEntry::flatten(self.options)
+    }
+}
+
+/// An option of a command, or a set of options flattened into it.
+#[derive(Debug, Clone, Copy)]
+pub enum OptionEntry {
+    Option(OptionMeta),
+    Flatten(&'static [OptionEntry]),
+}
+
+impl OptionEntry {
+    /// Returns the options of `entries`, with flattened sets replaced by their
+    /// options.
+    pub fn flatten(entries: &'static [OptionEntry]) -> Vec<&'static OptionMeta> {
+        fn collect(entries: &'static [OptionEntry], options: &mut Vec<&'static OptionMeta>) {
+            for entry in entries {
+                match entry {
+                    OptionEntry::Option(option) => options.push(option),
+                    OptionEntry::Flatten(entries) => collect(entries, options),
+                }
+            }
+        }
+
+        let mut options = Vec::new();
+        collect(entries, &mut options);
+        options
+    }
+}
+
+/// The structure of an option.
+///
+/// Options cannot be given constraints, such as ranges or choices, yet: the
+/// derives only accept their kind and name, so whether they are required is
+/// the only constraint they carry.
+#[derive(Debug, Clone, Copy)]
+pub struct OptionMeta {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub kind: OptionKind,
+    pub required: bool,
+}
+
+impl OptionMeta {
+    /// Returns a readable name for the type of the option.
+    pub fn kind_name(&self) -> &'static str {
+        match self.kind {
+            OptionKind::String => "text",
+            OptionKind::Integer => "integer",
+            OptionKind::Number => "number",
+            OptionKind::Boolean => "boolean",
+            OptionKind::User => "user",
+            OptionKind::Channel => "channel",
+            OptionKind::Role => "role",
+            OptionKind::Mentionable => "mention",
+            OptionKind::SubCommand | OptionKind::SubCommandGroup => "subcommand",
+        }
+    }
+}
+
+/// Returns the space-separated names of the invoked command and subcommands,
+/// as found by [`CommandMeta::find`].
+#[cfg(any(not(twilight), feature = "tracing"))]
+pub(crate) fn path(data: &CommandData) -> String {
+    let mut path = data.name.clone();
+    let mut options = data.options.as_slice();
+
+    while let Some((name, nested)) = options.first().and_then(subcommand_options) {
+        path.push(' ');
+        path.push_str(name);
+        options = nested;
+    }
+
+    path
+}
+
+/// Returns the name and options of `option` if it is a subcommand or a
+/// subcommand group.
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+fn subcommand_options(option: &CommandDataOption) -> Option<(&str, &[CommandDataOption])> {
+    match option.kind {
+        CommandOptionType::SubCommand | CommandOptionType::SubCommandGroup => {
+            Some((&option.name, &option.options))
+        },
+        _ => None,
+    }
+}
+
+#[cfg(serenity = "0_12")]
+fn subcommand_options(option: &CommandDataOption) -> Option<(&str, &[CommandDataOption])> {
+    match &option.value {
+        CommandDataOptionValue::SubCommand(o) | CommandDataOptionValue::SubCommandGroup(o) => {
+            Some((&option.name, o))
+        },
+        _ => None,
+    }
+}
+
+#[cfg(all(twilight, feature = "tracing"))]
+fn subcommand_options(option: &CommandDataOption) -> Option<(&str, &[CommandDataOption])> {
+    match &option.value {
+        CommandOptionValue::SubCommand(o) | CommandOptionValue::SubCommandGroup(o) => {
+            Some((&option.name, o))
+        },
+        _ => None,
+    }
+}
@@ -1,4 +1,79 @@
-pub extern crate serenity;
+pub extern crate serde;
+pub extern crate serde_json;
+#[cfg(serenity = "0_10")]
+pub extern crate serenity_0_10 as serenity;
+#[cfg(serenity = "0_11")]
+pub extern crate serenity_0_11 as serenity;
+#[cfg(serenity = "0_12")]
+pub extern crate serenity_0_12 as serenity;
 pub extern crate serenity_commands_derive as macros;
+#[cfg(twilight)]
+pub extern crate twilight_model;
 
+#[cfg(not(any(serenity = "0_10", serenity = "0_11", serenity = "0_12", twilight)))]
+compile_error!(
+    "one of the `serenity_0_10`, `serenity_0_11`, `serenity_0_12` and `twilight` features must \
+     be enabled"
+);
+
+// The default `serenity_0_10` feature stays enabled along with the one chosen
+// unless the default features are disabled, which would otherwise silently
+// select Serenity 0.10.
+#[cfg(any(
+    all(feature = "serenity_0_10", feature = "serenity_0_11"),
+    all(feature = "serenity_0_10", feature = "serenity_0_12"),
+    all(feature = "serenity_0_10", feature = "twilight"),
+    all(feature = "serenity_0_11", feature = "serenity_0_12"),
+    all(feature = "serenity_0_11", feature = "twilight"),
+    all(feature = "serenity_0_12", feature = "twilight"),
+))]
+compile_error!(
+    "only one of the `serenity_0_10`, `serenity_0_11`, `serenity_0_12` and `twilight` features \
+     can be enabled; set `default-features = false` to use another one than `serenity_0_10`"
+);
+
+#[cfg(all(feature = "endpoint", twilight))]
+compile_error!(
+    "the `endpoint` feature requires one of the `serenity_0_10`, `serenity_0_11` and \
+     `serenity_0_12` features"
+);
+
+// Lets the derives, which refer to `serenity_commands`, be used in this crate.
+extern crate self as serenity_commands;
+
+// The response builder, which is filled in place and borrows attachments
+// since Serenity 0.11.
+#[cfg(serenity = "0_10")]
+pub(crate) type CreateInteractionResponse<'a> = serenity::builder::CreateInteractionResponse;
+#[cfg(serenity = "0_11")]
+pub(crate) type CreateInteractionResponse<'a> = serenity::builder::CreateInteractionResponse<'a>;
+
+pub use macros::command;
+
+pub mod component;
+#[cfg(not(twilight))]
+pub mod context;
+#[cfg(not(twilight))]
+pub mod dispatch;
+#[cfg(all(feature = "endpoint", not(twilight)))]
+pub mod endpoint;
 pub mod error;
+pub mod help;
+pub mod info;
+pub mod message;
+#[cfg(all(feature = "metrics", not(twilight)))]
+pub mod metrics;
+pub mod modal;
+pub mod model;
+pub mod options;
+pub mod reference;
+#[cfg(not(twilight))]
+pub mod testing;
+#[cfg(feature = "tracing")]
+pub mod trace;
+#[cfg(twilight)]
+pub mod twilight;
+
+mod traits;
+
+pub use traits::{Command, Commands, Group, Options, SubCommand, SubCommandOption};
@@ -0,0 +1,233 @@
+//! Parsing of text commands, such as `!ping 3`, used by the `parse_message`
+//! functions generated for commands marked with `#[command(prefix)]`.
+//!
+//! Arguments are separated by whitespace, and may be wrapped in double quotes
+//! to contain whitespace. Options are filled by position, in the order of
+//! their declaration.
+//!
+//! A message carries no resolved data, so users, channels and roles parsed
+//! from mentions (`<@id>`, `<#id>` and `<@&id>`) or raw ids only have their id
+//! set. With Serenity 0.12 and Twilight, whose options hold ids rather than
+//! resolved values, only the ids are parsed, and mentionable options take
+//! either a user mention (`<@id>`), a role mention (`<@&id>`) or a raw id.
+
+#[cfg(any(serenity = "0_12", twilight))]
+use std::num::NonZeroU64;
+use std::vec::IntoIter;
+
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serde_json::json;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::model::channel::PartialChannel;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::model::guild::Role;
+#[cfg(serenity = "0_12")]
+use serenity::model::id::{ChannelId, GenericId, RoleId, UserId};
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::model::user::User;
+#[cfg(twilight)]
+use twilight_model::id::marker::{ChannelMarker, GenericMarker, RoleMarker, UserMarker};
+#[cfg(twilight)]
+use twilight_model::id::Id;
+
+use crate::error::ParseError;
+
+#[cfg(twilight)]
+type UserId = Id<UserMarker>;
+#[cfg(twilight)]
+type ChannelId = Id<ChannelMarker>;
+#[cfg(twilight)]
+type RoleId = Id<RoleMarker>;
+#[cfg(twilight)]
+type GenericId = Id<GenericMarker>;
+
+/// Splits the content of a message into its arguments.
+pub fn tokenize(content: &str) -> Result<Vec<String>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = content.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        let c = match chars.next() {
+            Some(c) => c,
+            None => return Ok(tokens),
+        };
+
+        let mut token = String::new();
+
+        if c == '"' {
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => match chars.next() {
+                        Some(c) => token.push(c),
+                        None => return Err(ParseError::UnclosedQuote),
+                    },
+                    Some(c) => token.push(c),
+                    None => return Err(ParseError::UnclosedQuote),
+                }
+            }
+        } else {
+            token.push(c);
+
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                token.push(c);
+            }
+        }
+
+        tokens.push(token);
+    }
+}
+
+fn parse_id(arg: &str, prefix: &str) -> Option<u64> {
+    let id = match arg.strip_prefix(prefix) {
+        Some(mention) => mention.strip_suffix('>')?,
+        None => arg,
+    };
+
+    id.parse().ok()
+}
+
+/// A command, subcommand or subcommand group that can be parsed out of the
+/// arguments of a text command.
+///
+/// This is implemented by `#[derive(Command)]` and `#[derive(Group)]` when
+/// `prefix` is given.
+pub trait FromArguments: Sized {
+    fn parse_arguments(args: &mut Arguments) -> Result<Self, ParseError>;
+}
+
+/// The arguments of a text command.
+#[derive(Debug)]
+pub struct Arguments {
+    tokens: IntoIter<String>,
+}
+
+impl Arguments {
+    pub fn new(content: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            tokens: tokenize(content)?.into_iter(),
+        })
+    }
+
+    /// Returns the name of the command or subcommand being invoked.
+    pub fn name(&mut self) -> Result<String, ParseError> {
+        self.tokens.next().ok_or(ParseError::MissingName)
+    }
+
+    /// Ensures that every argument has been consumed.
+    pub fn finish(mut self) -> Result<(), ParseError> {
+        match self.tokens.next() {
+            Some(arg) => Err(ParseError::UnexpectedArgument(arg)),
+            None => Ok(()),
+        }
+    }
+
+    fn parse<T>(
+        &mut self,
+        name: &'static str,
+        f: impl FnOnce(&str) -> Option<T>,
+    ) -> Result<Option<T>, ParseError> {
+        match self.tokens.next() {
+            Some(arg) => f(&arg).map(Some).ok_or(ParseError::InvalidArgument(name)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn string(&mut self, name: &'static str) -> Result<Option<String>, ParseError> {
+        self.parse(name, |arg| Some(arg.to_string()))
+    }
+
+    pub fn integer(&mut self, name: &'static str) -> Result<Option<i64>, ParseError> {
+        self.parse(name, |arg| arg.parse().ok())
+    }
+
+    pub fn number(&mut self, name: &'static str) -> Result<Option<f64>, ParseError> {
+        self.parse(name, |arg| arg.parse().ok())
+    }
+
+    pub fn boolean(&mut self, name: &'static str) -> Result<Option<bool>, ParseError> {
+        self.parse(name, |arg| match &arg.to_lowercase()[..] {
+            "true" | "yes" | "on" => Some(true),
+            "false" | "no" | "off" => Some(false),
+            _ => None,
+        })
+    }
+
+    #[cfg(any(serenity = "0_10", serenity = "0_11"))]
+    pub fn user(&mut self, name: &'static str) -> Result<Option<User>, ParseError> {
+        self.parse(name, |arg| {
+            let arg = arg.replacen("<@!", "<@", 1);
+            let id = parse_id(&arg, "<@")?;
+
+            let mut user = User::default();
+            user.id = id.into();
+            Some(user)
+        })
+    }
+
+    #[cfg(any(serenity = "0_10", serenity = "0_11"))]
+    pub fn channel(&mut self, name: &'static str) -> Result<Option<PartialChannel>, ParseError> {
+        self.parse(name, |arg| {
+            let id = parse_id(arg, "<#")?;
+
+            serde_json::from_value(json!({
+                "id": id.to_string(),
+                "name": "",
+                "type": 0,
+                "permissions": null,
+            }))
+            .ok()
+        })
+    }
+
+    #[cfg(any(serenity = "0_10", serenity = "0_11"))]
+    pub fn role(&mut self, name: &'static str) -> Result<Option<Role>, ParseError> {
+        self.parse(name, |arg| {
+            let id = parse_id(arg, "<@&")?;
+
+            serde_json::from_value(json!({
+                "id": id.to_string(),
+                "guild_id": "0",
+                "name": "",
+                "color": 0,
+                "hoist": false,
+                "managed": false,
+                "mentionable": false,
+                "permissions": "0",
+                "position": 0,
+            }))
+            .ok()
+        })
+    }
+
+    #[cfg(any(serenity = "0_12", twilight))]
+    pub fn user(&mut self, name: &'static str) -> Result<Option<UserId>, ParseError> {
+        self.parse(name, |arg| {
+            let arg = arg.replacen("<@!", "<@", 1);
+
+            parse_id(&arg, "<@").and_then(NonZeroU64::new).map(UserId::from)
+        })
+    }
+
+    #[cfg(any(serenity = "0_12", twilight))]
+    pub fn channel(&mut self, name: &'static str) -> Result<Option<ChannelId>, ParseError> {
+        self.parse(name, |arg| parse_id(arg, "<#").and_then(NonZeroU64::new).map(ChannelId::from))
+    }
+
+    #[cfg(any(serenity = "0_12", twilight))]
+    pub fn role(&mut self, name: &'static str) -> Result<Option<RoleId>, ParseError> {
+        self.parse(name, |arg| parse_id(arg, "<@&").and_then(NonZeroU64::new).map(RoleId::from))
+    }
+
+    /// Parses the id of a mentioned user or role, or a raw id.
+    #[cfg(any(serenity = "0_12", twilight))]
+    pub fn mention(&mut self, name: &'static str) -> Result<Option<GenericId>, ParseError> {
+        self.parse(name, |arg| {
+            let arg = arg.replacen("<@!", "<@", 1).replacen("<@&", "<@", 1);
+
+            parse_id(&arg, "<@").and_then(NonZeroU64::new).map(GenericId::from)
+        })
+    }
+}
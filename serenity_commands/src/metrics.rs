@@ -0,0 +1,57 @@
+//! Counters and histograms of the commands run by a
+//! [`Dispatcher`](crate::dispatch::Dispatcher), recorded through the `metrics`
+//! facade with the `metrics` feature, so that any installed exporter picks
+//! them up.
+//!
+//! | Name                 | Type      | Labels  |
+//! |----------------------|-----------|---------|
+//! | [`INVOCATIONS`]      | counter   | `path`  |
+//! | [`PARSE_ERRORS`]     | counter   | `error` |
+//! | [`CHECK_FAILURES`]   | counter   | `path`  |
+//! | [`HANDLER_DURATION`] | histogram | `path`  |
+//!
+//! `path` is the space-separated names of the command and its subcommands,
+//! such as `config prefix set`, and `error` is the [`ParseError::name`] of
+//! the error.
+
+use std::time::Duration;
+
+use crate::error::ParseError;
+
+/// The number of commands parsed and dispatched.
+pub const INVOCATIONS: &str = "serenity_commands_invocations_total";
+/// The number of interactions that failed to parse.
+pub const PARSE_ERRORS: &str = "serenity_commands_parse_errors_total";
+/// The number of commands stopped by a middleware.
+pub const CHECK_FAILURES: &str = "serenity_commands_check_failures_total";
+/// How long commands ran for, in seconds.
+pub const HANDLER_DURATION: &str = "serenity_commands_handler_duration_seconds";
+
+/// Describes the metrics to the installed recorder, which some exporters use
+/// to document them.
+pub fn describe() {
+    ::metrics::describe_counter!(INVOCATIONS, "The number of commands parsed and dispatched.");
+    ::metrics::describe_counter!(PARSE_ERRORS, "The number of interactions that failed to parse.");
+    ::metrics::describe_counter!(CHECK_FAILURES, "The number of commands stopped by a middleware.");
+    ::metrics::describe_histogram!(
+        HANDLER_DURATION,
+        ::metrics::Unit::Seconds,
+        "How long commands ran for."
+    );
+}
+
+pub(crate) fn parse_failed(error: &ParseError) {
+    ::metrics::counter!(PARSE_ERRORS, "error" => error.name()).increment(1);
+}
+
+pub(crate) fn invoked(path: &str) {
+    ::metrics::counter!(INVOCATIONS, "path" => path.to_string()).increment(1);
+}
+
+pub(crate) fn stopped(path: &str) {
+    ::metrics::counter!(CHECK_FAILURES, "path" => path.to_string()).increment(1);
+}
+
+pub(crate) fn ran(path: &str, elapsed: Duration) {
+    ::metrics::histogram!(HANDLER_DURATION, "path" => path.to_string()).record(elapsed);
+}
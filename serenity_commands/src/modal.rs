@@ -0,0 +1,259 @@
+//! Support for modals, used by `#[derive(Modal)]`.
+//!
+//! Serenity 0.10 does not know about modals, so the modal response is written
+//! into the response builder directly before Serenity 0.12, and submissions
+//! are parsed out of the raw payload of the interaction, such as the body of
+//! a request received by an HTTP interactions endpoint, or out of the modal
+//! submit interactions of Serenity 0.11 and later.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serde_json::json;
+use serde_json::Value;
+#[cfg(serenity = "0_12")]
+use serenity::builder::{CreateActionRow, CreateInputText, CreateInteractionResponse, CreateModal};
+#[cfg(serenity = "0_12")]
+use serenity::model::application::{
+    ActionRow,
+    ActionRowComponent,
+    InputTextStyle,
+    ModalInteraction,
+};
+#[cfg(serenity = "0_11")]
+use serenity::model::interactions::message_component::{ActionRow, ActionRowComponent};
+#[cfg(serenity = "0_11")]
+use serenity::model::interactions::modal::ModalSubmitInteraction as ModalInteraction;
+
+use crate::error::ParseError;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use crate::CreateInteractionResponse;
+
+const MODAL_SUBMIT: u64 = 5;
+
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+const MODAL: u8 = 9;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+const ACTION_ROW: u8 = 1;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+const TEXT_INPUT: u8 = 4;
+
+#[derive(Debug, Clone, Copy)]
+pub enum TextInputStyle {
+    Short = 1,
+    Paragraph = 2,
+}
+
+/// A text input of a modal.
+#[derive(Debug, Clone, Copy)]
+pub struct TextInput {
+    pub custom_id: &'static str,
+    pub label: &'static str,
+    pub style: TextInputStyle,
+    pub required: bool,
+    pub min_len: Option<u16>,
+    pub max_len: Option<u16>,
+    pub placeholder: Option<&'static str>,
+}
+
+impl TextInput {
+    #[cfg(any(serenity = "0_10", serenity = "0_11"))]
+    fn to_json(self) -> Value {
+        let mut input = json!({
+            "type": TEXT_INPUT,
+            "custom_id": self.custom_id,
+            "label": self.label,
+            "style": self.style as u8,
+            "required": self.required,
+        });
+
+        if let Some(min_len) = self.min_len {
+            input["min_length"] = json!(min_len);
+        }
+
+        if let Some(max_len) = self.max_len {
+            input["max_length"] = json!(max_len);
+        }
+
+        if let Some(placeholder) = self.placeholder {
+            input["placeholder"] = json!(placeholder);
+        }
+
+        input
+    }
+
+    #[cfg(serenity = "0_12")]
+    fn build(self) -> CreateInputText {
+        let style = match self.style {
+            TextInputStyle::Short => InputTextStyle::Short,
+            TextInputStyle::Paragraph => InputTextStyle::Paragraph,
+        };
+
+        let mut input =
+            CreateInputText::new(style, self.label, self.custom_id).required(self.required);
+
+        if let Some(min_len) = self.min_len {
+            input = input.min_length(min_len);
+        }
+
+        if let Some(max_len) = self.max_len {
+            input = input.max_length(max_len);
+        }
+
+        if let Some(placeholder) = self.placeholder {
+            input = input.placeholder(placeholder);
+        }
+
+        input
+    }
+}
+
+/// Sets `response` to show a modal with the given inputs, each placed in its
+/// own row.
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+pub fn create_modal<'a, 'b>(
+    response: &'a mut CreateInteractionResponse<'b>,
+    custom_id: &str,
+    title: &str,
+    inputs: &[TextInput],
+) -> &'a mut CreateInteractionResponse<'b> {
+    let rows = inputs
+        .iter()
+        .map(|input| {
+            json!({
+                "type": ACTION_ROW,
+                "components": [input.to_json()],
+            })
+        })
+        .collect::<Vec<_>>();
+
+    response.0.insert("type", json!(MODAL));
+    response.0.insert(
+        "data",
+        json!({
+            "custom_id": custom_id,
+            "title": title,
+            "components": rows,
+        }),
+    );
+
+    response
+}
+
+/// Creates a response showing a modal with the given inputs, each placed in
+/// its own row.
+#[cfg(serenity = "0_12")]
+pub fn create_modal(
+    custom_id: &str,
+    title: &str,
+    inputs: &[TextInput],
+) -> CreateInteractionResponse {
+    let rows = inputs.iter().map(|input| CreateActionRow::InputText(input.build())).collect();
+
+    CreateInteractionResponse::Modal(CreateModal::new(custom_id, title).components(rows))
+}
+
+#[derive(Deserialize)]
+struct SubmitData {
+    custom_id: String,
+    components: Vec<SubmitRow>,
+}
+
+#[derive(Deserialize)]
+struct SubmitRow {
+    components: Vec<SubmitInput>,
+}
+
+#[derive(Deserialize)]
+struct SubmitInput {
+    custom_id: String,
+    #[serde(default)]
+    value: String,
+}
+
+/// The values submitted through a modal.
+#[derive(Debug, Clone)]
+pub struct ModalSubmit {
+    pub custom_id: String,
+    pub values: HashMap<String, String>,
+}
+
+impl ModalSubmit {
+    /// Extracts the submission out of the payload of a modal submit
+    /// interaction.
+    pub fn from_json(interaction: &Value) -> Result<Self, ParseError> {
+        if interaction.get("type").and_then(Value::as_u64) != Some(MODAL_SUBMIT) {
+            return Err(ParseError::InvalidPayload(
+                "expected a modal submit interaction".to_string(),
+            ));
+        }
+
+        match interaction.get("data") {
+            Some(data) => Self::from_data(data),
+            None => Err(ParseError::InvalidPayload("expected data".to_string())),
+        }
+    }
+
+    /// Extracts the submission out of the `data` of a modal submit
+    /// interaction.
+    pub fn from_data(data: &Value) -> Result<Self, ParseError> {
+        let data =
+            SubmitData::deserialize(data).map_err(|e| ParseError::InvalidPayload(e.to_string()))?;
+
+        let values = data
+            .components
+            .into_iter()
+            .flat_map(|row| row.components)
+            .map(|input| (input.custom_id, input.value))
+            .collect();
+
+        Ok(Self {
+            custom_id: data.custom_id,
+            values,
+        })
+    }
+
+    /// Extracts the submission out of a modal submit interaction.
+    #[cfg(any(serenity = "0_11", serenity = "0_12"))]
+    pub fn from_interaction(interaction: &ModalInteraction) -> Self {
+        Self {
+            custom_id: interaction.data.custom_id.clone(),
+            values: input_values(&interaction.data.components),
+        }
+    }
+
+    /// Removes the value of an input, treating an empty value, which Discord
+    /// sends for optional inputs left blank, as missing.
+    pub fn take(&mut self, custom_id: &str) -> Option<String> {
+        self.values.remove(custom_id).filter(|v| !v.is_empty())
+    }
+}
+
+#[cfg(serenity = "0_11")]
+fn input_values(rows: &[ActionRow]) -> HashMap<String, String> {
+    rows.iter()
+        .flat_map(|row| &row.components)
+        .filter_map(|component| match component {
+            ActionRowComponent::InputText(input) => {
+                Some((input.custom_id.clone(), input.value.clone()))
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+// Serenity 0.12 leaves the value out when Discord does, as it does not
+// distinguish submitted inputs from the ones of the modal being created.
+#[cfg(serenity = "0_12")]
+fn input_values(rows: &[ActionRow]) -> HashMap<String, String> {
+    rows.iter()
+        .flat_map(|row| &row.components)
+        .filter_map(|component| match component {
+            ActionRowComponent::InputText(input) => {
+                Some((input.custom_id.clone(), input.value.clone().unwrap_or_default()))
+            },
+            _ => None,
+        })
+        .collect()
+}
@@ -0,0 +1,675 @@
+//! A representation of commands and of the options they are invoked with that
+//! belongs to this crate rather than to the targeted library.
+//!
+//! The derives register commands through [`CommandDefinition`]s, built out of
+//! their metadata and then converted into the builders of the library, and
+//! interactions can be converted into [`InteractionOptions`] and back. Both
+//! serialize to the JSON used by Discord, so they can be exported, or written
+//! by hand in tests.
+//!
+//! The derives parse commands out of [`InteractionOptions`], which the data of
+//! an interaction is converted into once. On Serenity 0.10 and 0.11, whose
+//! options hold the users, channels and roles they refer to, the converted
+//! options keep them, while options converted from [`InteractionOptions`]
+//! only hold their id, and are not resolved.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::builder::{
+    CreateApplicationCommand as CreateCommand,
+    CreateApplicationCommandOption as CreateCommandOption,
+};
+#[cfg(serenity = "0_12")]
+use serenity::builder::{CreateCommand, CreateCommandOption};
+#[cfg(serenity = "0_12")]
+use serenity::model::application::{
+    Command,
+    CommandData,
+    CommandDataOption,
+    CommandOption,
+    CommandOptionType,
+};
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::model::interactions::application_command::{
+    ApplicationCommand as Command,
+    ApplicationCommandInteractionData as CommandData,
+    ApplicationCommandInteractionDataOption as CommandDataOption,
+    ApplicationCommandInteractionDataOptionValue as ResolvedValue,
+    ApplicationCommandOption as CommandOption,
+    ApplicationCommandOptionType as CommandOptionType,
+};
+#[cfg(any(serenity = "0_11", serenity = "0_12"))]
+use serenity::model::Permissions;
+#[cfg(twilight)]
+use twilight_model::application::command::{Command, CommandOption, CommandOptionType};
+#[cfg(twilight)]
+use twilight_model::application::interaction::application_command::{
+    CommandData,
+    CommandDataOption,
+};
+#[cfg(twilight)]
+use twilight_model::guild::Permissions;
+
+use crate::error::ParseError;
+use crate::info::{CommandMeta, OptionEntry, OptionMeta};
+
+/// The type of an option, numbered as by Discord.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum OptionKind {
+    SubCommand = 1,
+    SubCommandGroup = 2,
+    String = 3,
+    Integer = 4,
+    Boolean = 5,
+    User = 6,
+    Channel = 7,
+    Role = 8,
+    Mentionable = 9,
+    Number = 10,
+}
+
+impl From<OptionKind> for u8 {
+    fn from(kind: OptionKind) -> Self {
+        kind as u8
+    }
+}
+
+impl TryFrom<u8> for OptionKind {
+    type Error = ParseError;
+
+    fn try_from(n: u8) -> Result<Self, Self::Error> {
+        Ok(match n {
+            1 => Self::SubCommand,
+            2 => Self::SubCommandGroup,
+            3 => Self::String,
+            4 => Self::Integer,
+            5 => Self::Boolean,
+            6 => Self::User,
+            7 => Self::Channel,
+            8 => Self::Role,
+            9 => Self::Mentionable,
+            10 => Self::Number,
+            n => return Err(ParseError::InvalidPayload(format!("unsupported option type {}", n))),
+        })
+    }
+}
+
+impl From<OptionKind> for CommandOptionType {
+    fn from(kind: OptionKind) -> Self {
+        match kind {
+            OptionKind::SubCommand => Self::SubCommand,
+            OptionKind::SubCommandGroup => Self::SubCommandGroup,
+            OptionKind::String => Self::String,
+            OptionKind::Integer => Self::Integer,
+            OptionKind::Boolean => Self::Boolean,
+            OptionKind::User => Self::User,
+            OptionKind::Channel => Self::Channel,
+            OptionKind::Role => Self::Role,
+            OptionKind::Mentionable => Self::Mentionable,
+            OptionKind::Number => Self::Number,
+        }
+    }
+}
+
+impl TryFrom<CommandOptionType> for OptionKind {
+    type Error = ParseError;
+
+    #[cfg(not(serenity = "0_12"))]
+    fn try_from(kind: CommandOptionType) -> Result<Self, Self::Error> {
+        Self::try_from(kind as u8)
+    }
+
+    #[cfg(serenity = "0_12")]
+    fn try_from(kind: CommandOptionType) -> Result<Self, Self::Error> {
+        Self::try_from(u8::from(kind))
+    }
+}
+
+/// A command, as registered with Discord.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommandDefinition {
+    pub name: String,
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<OptionDefinition>,
+    /// The bits of the permissions members need to use the command.
+    #[serde(default, with = "permission_bits", skip_serializing_if = "Option::is_none")]
+    pub default_member_permissions: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dm_permission: Option<bool>,
+}
+
+/// An option of a command, which is either a value, a subcommand or a
+/// subcommand group.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OptionDefinition {
+    #[serde(rename = "type")]
+    pub kind: OptionKind,
+    pub name: String,
+    pub description: String,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub required: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<OptionDefinition>,
+}
+
+fn is_false(b: &bool) -> bool {
+    !b
+}
+
+/// Serializes permissions as the string of their bits, as Discord does.
+mod permission_bits {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bits: &Option<u64>, serializer: S) -> Result<S::Ok, S::Error> {
+        match bits {
+            Some(bits) => serializer.serialize_str(&bits.to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<u64>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|bits| bits.parse().map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
+
+impl OptionDefinition {
+    /// Returns the definitions of the options of `entries`, with flattened sets
+    /// replaced by their options.
+    pub fn from_entries(entries: &'static [OptionEntry]) -> Vec<Self> {
+        OptionEntry::flatten(entries).into_iter().map(Self::from).collect()
+    }
+}
+
+/// Returns the definitions of the options and subcommands of `meta`.
+fn sub_options(meta: &CommandMeta) -> Vec<OptionDefinition> {
+    let options = meta.options().into_iter().map(OptionDefinition::from);
+    let subcommands = meta.subcommands.iter().copied().map(OptionDefinition::from);
+
+    options.chain(subcommands).collect()
+}
+
+impl From<&CommandMeta> for CommandDefinition {
+    fn from(meta: &CommandMeta) -> Self {
+        Self {
+            name: meta.name.to_string(),
+            description: meta.description.to_string(),
+            options: sub_options(meta),
+            default_member_permissions: meta.default_member_permissions.map(|p| p.bits),
+            dm_permission: meta.dm_permission,
+        }
+    }
+}
+
+/// Creates a subcommand, or a subcommand group if `meta` has subcommands.
+impl From<&CommandMeta> for OptionDefinition {
+    fn from(meta: &CommandMeta) -> Self {
+        let kind = if meta.subcommands.is_empty() {
+            OptionKind::SubCommand
+        } else {
+            OptionKind::SubCommandGroup
+        };
+
+        Self {
+            kind,
+            name: meta.name.to_string(),
+            description: meta.description.to_string(),
+            required: false,
+            options: sub_options(meta),
+        }
+    }
+}
+
+impl From<&OptionMeta> for OptionDefinition {
+    fn from(meta: &OptionMeta) -> Self {
+        Self {
+            kind: meta.kind,
+            name: meta.name.to_string(),
+            description: meta.description.to_string(),
+            required: meta.required,
+            options: Vec::new(),
+        }
+    }
+}
+
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+impl CommandDefinition {
+    /// Fills `cmd` with the command.
+    pub fn register<'a>(&self, cmd: &'a mut CreateCommand) -> &'a mut CreateCommand {
+        cmd.name(&self.name).description(&self.description);
+
+        for option in &self.options {
+            cmd.create_option(|opt| option.register(opt));
+        }
+
+        // Serenity 0.10 predates these fields, which are set in its map.
+        #[cfg(serenity = "0_10")]
+        {
+            if let Some(bits) = self.default_member_permissions {
+                cmd.0.insert("default_member_permissions", Value::String(bits.to_string()));
+            }
+
+            if let Some(dm_permission) = self.dm_permission {
+                cmd.0.insert("dm_permission", Value::Bool(dm_permission));
+            }
+        }
+
+        #[cfg(serenity = "0_11")]
+        {
+            if let Some(bits) = self.default_member_permissions {
+                cmd.default_member_permissions(Permissions::from_bits_truncate(bits));
+            }
+
+            if let Some(dm_permission) = self.dm_permission {
+                cmd.dm_permission(dm_permission);
+            }
+        }
+
+        cmd
+    }
+}
+
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+impl OptionDefinition {
+    /// Fills `opt` with the option.
+    pub fn register<'a>(&self, opt: &'a mut CreateCommandOption) -> &'a mut CreateCommandOption {
+        opt.name(&self.name).description(&self.description).kind(self.kind.into());
+
+        if self.required {
+            opt.required(true);
+        }
+
+        for option in &self.options {
+            opt.create_sub_option(|sub| option.register(sub));
+        }
+
+        opt
+    }
+}
+
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+impl From<CommandDefinition> for CreateCommand {
+    fn from(definition: CommandDefinition) -> Self {
+        let mut cmd = Self::default();
+        definition.register(&mut cmd);
+        cmd
+    }
+}
+
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+impl From<OptionDefinition> for CreateCommandOption {
+    fn from(definition: OptionDefinition) -> Self {
+        let mut opt = Self::default();
+        definition.register(&mut opt);
+        opt
+    }
+}
+
+#[cfg(serenity = "0_12")]
+impl From<CommandDefinition> for CreateCommand {
+    fn from(definition: CommandDefinition) -> Self {
+        let mut cmd = Self::new(definition.name).description(definition.description);
+
+        if let Some(bits) = definition.default_member_permissions {
+            cmd = cmd.default_member_permissions(Permissions::from_bits_truncate(bits));
+        }
+
+        if let Some(dm_permission) = definition.dm_permission {
+            #[allow(deprecated)]
+            {
+                cmd = cmd.dm_permission(dm_permission);
+            }
+        }
+
+        definition.options.into_iter().fold(cmd, |cmd, option| cmd.add_option(option.into()))
+    }
+}
+
+#[cfg(serenity = "0_12")]
+impl From<OptionDefinition> for CreateCommandOption {
+    fn from(definition: OptionDefinition) -> Self {
+        let mut opt = Self::new(definition.kind.into(), definition.name, definition.description);
+
+        if definition.required {
+            opt = opt.required(true);
+        }
+
+        definition.options.into_iter().fold(opt, |opt, option| opt.add_sub_option(option.into()))
+    }
+}
+
+#[cfg(twilight)]
+impl From<CommandDefinition> for Command {
+    fn from(definition: CommandDefinition) -> Self {
+        let mut cmd = crate::twilight::command(&definition.name, &definition.description);
+        cmd.default_member_permissions =
+            definition.default_member_permissions.map(Permissions::from_bits_truncate);
+
+        #[allow(deprecated)]
+        {
+            cmd.dm_permission = definition.dm_permission;
+        }
+
+        for option in definition.options {
+            crate::twilight::add_option(&mut cmd, option.into());
+        }
+
+        cmd
+    }
+}
+
+#[cfg(twilight)]
+impl From<OptionDefinition> for CommandOption {
+    fn from(definition: OptionDefinition) -> Self {
+        let mut opt = crate::twilight::option(
+            definition.kind.into(),
+            &definition.name,
+            &definition.description,
+        );
+
+        if definition.required {
+            opt.required = Some(true);
+        }
+
+        for option in definition.options {
+            crate::twilight::add_sub_option(&mut opt, option.into());
+        }
+
+        opt
+    }
+}
+
+/// Reads a command returned by Discord once registered, failing if it has
+/// options of a type the model does not support.
+impl TryFrom<&Command> for CommandDefinition {
+    type Error = ParseError;
+
+    fn try_from(cmd: &Command) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: cmd.name.clone(),
+            description: cmd.description.clone(),
+            options: cmd.options.iter().map(TryFrom::try_from).collect::<Result<_, _>>()?,
+            #[cfg(serenity = "0_10")]
+            default_member_permissions: None,
+            #[cfg(not(serenity = "0_10"))]
+            default_member_permissions: cmd.default_member_permissions.map(|p| p.bits()),
+            #[cfg(serenity = "0_10")]
+            dm_permission: None,
+            #[cfg(not(serenity = "0_10"))]
+            #[allow(deprecated)]
+            dm_permission: cmd.dm_permission,
+        })
+    }
+}
+
+#[cfg(not(twilight))]
+impl TryFrom<&CommandOption> for OptionDefinition {
+    type Error = ParseError;
+
+    fn try_from(opt: &CommandOption) -> Result<Self, Self::Error> {
+        Ok(Self {
+            kind: opt.kind.try_into()?,
+            name: opt.name.clone(),
+            description: opt.description.clone(),
+            required: opt.required,
+            options: opt.options.iter().map(TryFrom::try_from).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[cfg(twilight)]
+impl TryFrom<&CommandOption> for OptionDefinition {
+    type Error = ParseError;
+
+    fn try_from(opt: &CommandOption) -> Result<Self, Self::Error> {
+        let options = opt.options.as_deref().unwrap_or_default();
+
+        Ok(Self {
+            kind: opt.kind.try_into()?,
+            name: opt.name.clone(),
+            description: opt.description.clone(),
+            required: opt.required.unwrap_or(false),
+            options: options.iter().map(TryFrom::try_from).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// The name of an invoked command and the options it was invoked with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InteractionOptions {
+    pub name: String,
+    #[serde(default)]
+    pub options: Vec<InteractionOption>,
+}
+
+/// An option an interaction was invoked with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "RawOption", into = "RawOption")]
+pub struct InteractionOption {
+    pub name: String,
+    pub value: InteractionValue,
+    /// The user, channel or role the option refers to, or its value, as
+    /// resolved by Serenity. It is missing for options that are focused for
+    /// autocompletion, and for options created by this crate out of an id.
+    #[cfg(any(serenity = "0_10", serenity = "0_11"))]
+    pub resolved: Option<ResolvedValue>,
+}
+
+impl InteractionOption {
+    pub fn new(name: impl Into<String>, value: InteractionValue) -> Self {
+        Self {
+            name: name.into(),
+            value,
+            #[cfg(any(serenity = "0_10", serenity = "0_11"))]
+            resolved: None,
+        }
+    }
+
+    /// Returns the options of a subcommand or subcommand group, or nothing
+    /// for other options.
+    pub fn options(&self) -> &[InteractionOption] {
+        match &self.value {
+            InteractionValue::SubCommand(options) | InteractionValue::SubCommandGroup(options) => {
+                options
+            },
+            _ => &[],
+        }
+    }
+}
+
+/// Options are compared by their name and value, which the resolved values of
+/// Serenity 0.10 and 0.11 are derived from.
+impl PartialEq for InteractionOption {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.value == other.value
+    }
+}
+
+/// The value of an option, where users, channels, roles and mentionables are
+/// referred to by their id.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InteractionValue {
+    SubCommand(Vec<InteractionOption>),
+    SubCommandGroup(Vec<InteractionOption>),
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+    User(u64),
+    Channel(u64),
+    Role(u64),
+    Mentionable(u64),
+    Number(f64),
+}
+
+impl InteractionValue {
+    pub fn kind(&self) -> OptionKind {
+        match self {
+            Self::SubCommand(_) => OptionKind::SubCommand,
+            Self::SubCommandGroup(_) => OptionKind::SubCommandGroup,
+            Self::String(_) => OptionKind::String,
+            Self::Integer(_) => OptionKind::Integer,
+            Self::Boolean(_) => OptionKind::Boolean,
+            Self::User(_) => OptionKind::User,
+            Self::Channel(_) => OptionKind::Channel,
+            Self::Role(_) => OptionKind::Role,
+            Self::Mentionable(_) => OptionKind::Mentionable,
+            Self::Number(_) => OptionKind::Number,
+        }
+    }
+
+    /// Returns the id of the user, channel, role or mentionable the value
+    /// refers to.
+    pub fn id(&self) -> Option<u64> {
+        match self {
+            Self::User(id) | Self::Channel(id) | Self::Role(id) | Self::Mentionable(id) => {
+                Some(*id)
+            },
+            _ => None,
+        }
+    }
+}
+
+/// An option as sent by Discord, with its value and nested options apart.
+#[derive(Serialize, Deserialize)]
+struct RawOption {
+    name: String,
+    #[serde(rename = "type")]
+    kind: OptionKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    value: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    options: Option<Vec<InteractionOption>>,
+}
+
+impl From<InteractionOption> for RawOption {
+    fn from(option: InteractionOption) -> Self {
+        let kind = option.value.kind();
+
+        let (value, options) = match option.value {
+            InteractionValue::SubCommand(options) | InteractionValue::SubCommandGroup(options) => {
+                (None, Some(options))
+            },
+            InteractionValue::String(s) => (Some(json!(s)), None),
+            InteractionValue::Integer(n) => (Some(json!(n)), None),
+            InteractionValue::Boolean(b) => (Some(json!(b)), None),
+            InteractionValue::Number(n) => (Some(json!(n)), None),
+            InteractionValue::User(id)
+            | InteractionValue::Channel(id)
+            | InteractionValue::Role(id)
+            | InteractionValue::Mentionable(id) => (Some(json!(id.to_string())), None),
+        };
+
+        Self {
+            name: option.name,
+            kind,
+            value,
+            options,
+        }
+    }
+}
+
+impl TryFrom<RawOption> for InteractionOption {
+    type Error = ParseError;
+
+    fn try_from(raw: RawOption) -> Result<Self, Self::Error> {
+        let invalid = || ParseError::InvalidType(raw.kind.into());
+
+        let value = raw.value.as_ref();
+        let id = || {
+            let id = value?;
+            id.as_str().and_then(|s| s.parse().ok()).or_else(|| id.as_u64())
+        };
+
+        let value = match raw.kind {
+            OptionKind::SubCommand => InteractionValue::SubCommand(raw.options.unwrap_or_default()),
+            OptionKind::SubCommandGroup => {
+                InteractionValue::SubCommandGroup(raw.options.unwrap_or_default())
+            },
+            OptionKind::String => {
+                InteractionValue::String(value.and_then(Value::as_str).ok_or_else(invalid)?.into())
+            },
+            OptionKind::Integer => {
+                InteractionValue::Integer(value.and_then(Value::as_i64).ok_or_else(invalid)?)
+            },
+            OptionKind::Boolean => {
+                InteractionValue::Boolean(value.and_then(Value::as_bool).ok_or_else(invalid)?)
+            },
+            OptionKind::Number => {
+                InteractionValue::Number(value.and_then(Value::as_f64).ok_or_else(invalid)?)
+            },
+            OptionKind::User => InteractionValue::User(id().ok_or_else(invalid)?),
+            OptionKind::Channel => InteractionValue::Channel(id().ok_or_else(invalid)?),
+            OptionKind::Role => InteractionValue::Role(id().ok_or_else(invalid)?),
+            OptionKind::Mentionable => InteractionValue::Mentionable(id().ok_or_else(invalid)?),
+        };
+
+        Ok(Self::new(raw.name, value))
+    }
+}
+
+/// Converts the data of an interaction, which the derives parse commands out
+/// of.
+impl TryFrom<&CommandData> for InteractionOptions {
+    type Error = ParseError;
+
+    fn try_from(data: &CommandData) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: data.name.clone(),
+            options: data.options.iter().map(TryFrom::try_from).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// Converts an option, keeping the value Serenity resolved it to.
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+impl TryFrom<&CommandDataOption> for InteractionOption {
+    type Error = ParseError;
+
+    fn try_from(option: &CommandDataOption) -> Result<Self, Self::Error> {
+        let raw = RawOption {
+            name: option.name.clone(),
+            kind: option.kind.try_into()?,
+            value: option.value.clone(),
+            options: Some(option.options.iter().map(TryFrom::try_from).collect::<Result<_, _>>()?),
+        };
+
+        Ok(Self {
+            resolved: option.resolved.clone(),
+            ..Self::try_from(raw)?
+        })
+    }
+}
+
+/// Converts an option through the payload Discord sends for it.
+#[cfg(any(serenity = "0_12", twilight))]
+impl TryFrom<&CommandDataOption> for InteractionOption {
+    type Error = ParseError;
+
+    fn try_from(option: &CommandDataOption) -> Result<Self, Self::Error> {
+        serde_json::to_value(option)
+            .and_then(serde_json::from_value)
+            .map_err(|e| ParseError::InvalidPayload(e.to_string()))
+    }
+}
+
+/// Creates the data of an interaction invoking the command with the options,
+/// as deserialized from the payload Discord would have sent.
+impl TryFrom<&InteractionOptions> for CommandData {
+    type Error = ParseError;
+
+    fn try_from(options: &InteractionOptions) -> Result<Self, Self::Error> {
+        serde_json::from_value(json!({
+            "id": "1",
+            "name": options.name,
+            "type": 1,
+            "options": options.options,
+        }))
+        .map_err(|e| ParseError::InvalidPayload(e.to_string()))
+    }
+}
@@ -0,0 +1,259 @@
+//! Conversion between the options of an interaction and the values of the
+//! fields of commands, used by the derives.
+//!
+//! Commands are parsed out of [`InteractionOption`]s, which the data of an
+//! interaction is converted into once by [`InteractionOptions`]. Users,
+//! channels and roles are parsed into the values resolved by Serenity 0.10
+//! and 0.11, and into their ids with later versions and Twilight.
+//!
+//! [`InteractionOptions`]: crate::model::InteractionOptions
+
+#[cfg(any(serenity = "0_12", twilight))]
+use std::num::NonZeroU64;
+
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::model::channel::PartialChannel as Channel;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::model::guild::Role;
+#[cfg(serenity = "0_12")]
+use serenity::model::id::{
+    ChannelId as Channel,
+    GenericId as Mentionable,
+    RoleId as Role,
+    UserId as User,
+};
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::model::interactions::application_command::ApplicationCommandInteractionDataOptionValue as ResolvedValue;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::model::user::User;
+#[cfg(twilight)]
+use twilight_model::id::marker::{ChannelMarker, GenericMarker, RoleMarker, UserMarker};
+#[cfg(twilight)]
+use twilight_model::id::Id;
+
+use crate::error::ParseError;
+use crate::model::{InteractionOption, InteractionValue, OptionKind};
+
+#[cfg(twilight)]
+type User = Id<UserMarker>;
+#[cfg(twilight)]
+type Channel = Id<ChannelMarker>;
+#[cfg(twilight)]
+type Role = Id<RoleMarker>;
+#[cfg(twilight)]
+type Mentionable = Id<GenericMarker>;
+
+fn invalid(kind: OptionKind) -> ParseError {
+    ParseError::InvalidType(kind.into())
+}
+
+/// Parses the value of a string option.
+///
+/// Like the other parsing functions, it returns `None` for options without a
+/// value to parse, which are then treated as missing.
+pub fn parse_string(option: InteractionOption) -> Result<Option<String>, ParseError> {
+    match option.value {
+        InteractionValue::String(s) => Ok(Some(s)),
+        _ => Err(invalid(OptionKind::String)),
+    }
+}
+
+/// Parses the value of an integer option.
+pub fn parse_integer(option: InteractionOption) -> Result<Option<i64>, ParseError> {
+    match option.value {
+        InteractionValue::Integer(n) => Ok(Some(n)),
+        _ => Err(invalid(OptionKind::Integer)),
+    }
+}
+
+/// Parses the value of a number option.
+pub fn parse_number(option: InteractionOption) -> Result<Option<f64>, ParseError> {
+    match option.value {
+        InteractionValue::Number(n) => Ok(Some(n)),
+        _ => Err(invalid(OptionKind::Number)),
+    }
+}
+
+/// Parses the value of a boolean option.
+pub fn parse_boolean(option: InteractionOption) -> Result<Option<bool>, ParseError> {
+    match option.value {
+        InteractionValue::Boolean(b) => Ok(Some(b)),
+        _ => Err(invalid(OptionKind::Boolean)),
+    }
+}
+
+// Options focused for autocompletion are not resolved by Serenity, so they
+// have no value to parse.
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+fn parse_resolved<T>(
+    option: InteractionOption,
+    kind: OptionKind,
+    extract: impl FnOnce(ResolvedValue) -> Option<T>,
+) -> Result<Option<T>, ParseError> {
+    if option.value.kind() != kind {
+        return Err(invalid(kind));
+    }
+
+    match option.resolved {
+        Some(value) => extract(value).map(Some).ok_or_else(|| invalid(kind)),
+        None => Ok(None),
+    }
+}
+
+/// Parses the user of a user option.
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+pub fn parse_user(option: InteractionOption) -> Result<Option<User>, ParseError> {
+    parse_resolved(option, OptionKind::User, |value| match value {
+        ResolvedValue::User(user, _) => Some(user),
+        _ => None,
+    })
+}
+
+/// Parses the channel of a channel option.
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+pub fn parse_channel(option: InteractionOption) -> Result<Option<Channel>, ParseError> {
+    parse_resolved(option, OptionKind::Channel, |value| match value {
+        ResolvedValue::Channel(channel) => Some(channel),
+        _ => None,
+    })
+}
+
+/// Parses the role of a role option.
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+pub fn parse_role(option: InteractionOption) -> Result<Option<Role>, ParseError> {
+    parse_resolved(option, OptionKind::Role, |value| match value {
+        ResolvedValue::Role(role) => Some(role),
+        _ => None,
+    })
+}
+
+#[cfg(any(serenity = "0_12", twilight))]
+fn parse_id<T: From<NonZeroU64>>(
+    option: InteractionOption,
+    kind: OptionKind,
+) -> Result<Option<T>, ParseError> {
+    if option.value.kind() != kind {
+        return Err(invalid(kind));
+    }
+
+    match option.value.id().and_then(NonZeroU64::new) {
+        Some(id) => Ok(Some(T::from(id))),
+        None => Err(invalid(kind)),
+    }
+}
+
+/// Parses the id of the user of a user option.
+#[cfg(any(serenity = "0_12", twilight))]
+pub fn parse_user(option: InteractionOption) -> Result<Option<User>, ParseError> {
+    parse_id(option, OptionKind::User)
+}
+
+/// Parses the id of the channel of a channel option.
+#[cfg(any(serenity = "0_12", twilight))]
+pub fn parse_channel(option: InteractionOption) -> Result<Option<Channel>, ParseError> {
+    parse_id(option, OptionKind::Channel)
+}
+
+/// Parses the id of the role of a role option.
+#[cfg(any(serenity = "0_12", twilight))]
+pub fn parse_role(option: InteractionOption) -> Result<Option<Role>, ParseError> {
+    parse_id(option, OptionKind::Role)
+}
+
+/// Parses the id of the user or role of a mentionable option.
+#[cfg(any(serenity = "0_12", twilight))]
+pub fn parse_mention(option: InteractionOption) -> Result<Option<Mentionable>, ParseError> {
+    parse_id(option, OptionKind::Mentionable)
+}
+
+/// Creates a string option holding `value`.
+pub fn string_option(name: &str, value: &str) -> InteractionOption {
+    InteractionOption::new(name, InteractionValue::String(value.to_string()))
+}
+
+/// Creates an integer option holding `value`.
+pub fn integer_option(name: &str, value: &i64) -> InteractionOption {
+    InteractionOption::new(name, InteractionValue::Integer(*value))
+}
+
+/// Creates a number option holding `value`.
+pub fn number_option(name: &str, value: &f64) -> InteractionOption {
+    InteractionOption::new(name, InteractionValue::Number(*value))
+}
+
+/// Creates a boolean option holding `value`.
+pub fn boolean_option(name: &str, value: &bool) -> InteractionOption {
+    InteractionOption::new(name, InteractionValue::Boolean(*value))
+}
+
+/// Creates a user option referring to `user`, which is kept as resolved.
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+pub fn user_option(name: &str, user: &User) -> InteractionOption {
+    InteractionOption {
+        resolved: Some(ResolvedValue::User(user.clone(), None)),
+        ..InteractionOption::new(name, InteractionValue::User(user.id.0))
+    }
+}
+
+/// Creates a channel option referring to `channel`, which is kept as
+/// resolved.
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+pub fn channel_option(name: &str, channel: &Channel) -> InteractionOption {
+    InteractionOption {
+        resolved: Some(ResolvedValue::Channel(channel.clone())),
+        ..InteractionOption::new(name, InteractionValue::Channel(channel.id.0))
+    }
+}
+
+/// Creates a role option referring to `role`, which is kept as resolved.
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+pub fn role_option(name: &str, role: &Role) -> InteractionOption {
+    InteractionOption {
+        resolved: Some(ResolvedValue::Role(role.clone())),
+        ..InteractionOption::new(name, InteractionValue::Role(role.id.0))
+    }
+}
+
+/// Creates a user option referring to `user`.
+#[cfg(any(serenity = "0_12", twilight))]
+pub fn user_option(name: &str, user: &User) -> InteractionOption {
+    InteractionOption::new(name, InteractionValue::User(user.get()))
+}
+
+/// Creates a channel option referring to `channel`.
+#[cfg(any(serenity = "0_12", twilight))]
+pub fn channel_option(name: &str, channel: &Channel) -> InteractionOption {
+    InteractionOption::new(name, InteractionValue::Channel(channel.get()))
+}
+
+/// Creates a role option referring to `role`.
+#[cfg(any(serenity = "0_12", twilight))]
+pub fn role_option(name: &str, role: &Role) -> InteractionOption {
+    InteractionOption::new(name, InteractionValue::Role(role.get()))
+}
+
+/// Creates a mentionable option referring to `mention`.
+#[cfg(any(serenity = "0_12", twilight))]
+pub fn mention_option(name: &str, mention: &Mentionable) -> InteractionOption {
+    InteractionOption::new(name, InteractionValue::Mentionable(mention.get()))
+}
+
+/// Creates a subcommand option, holding the options of the subcommand.
+pub fn subcommand_option(name: &str, options: Vec<InteractionOption>) -> InteractionOption {
+    InteractionOption::new(name, InteractionValue::SubCommand(options))
+}
+
+/// Creates a subcommand group option, holding one of its subcommands.
+pub fn subcommand_group_option(name: &str, options: Vec<InteractionOption>) -> InteractionOption {
+    InteractionOption::new(name, InteractionValue::SubCommandGroup(options))
+}
+
+/// Returns the options nested in a subcommand or subcommand group option.
+pub fn sub_options(option: InteractionOption) -> Vec<InteractionOption> {
+    match option.value {
+        InteractionValue::SubCommand(options) | InteractionValue::SubCommandGroup(options) => {
+            options
+        },
+        _ => Vec::new(),
+    }
+}
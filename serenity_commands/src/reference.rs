@@ -0,0 +1,200 @@
+//! Generation of a reference of commands, in Markdown or HTML, from their
+//! [`CommandMeta`], with their options and the permissions they require.
+//!
+//! A bot can expose this as a mode of its binary with [`run`]:
+//!
+//! ```ignore
+//! fn main() -> std::io::Result<()> {
+//!     let mut args = std::env::args().skip(1);
+//!
+//!     if args.next().as_deref() == Some("reference") {
+//!         // e.g. `bot reference --format html --output commands.html`
+//!         return serenity_commands::reference::run::<AllCommands>(args);
+//!     }
+//!
+//!     // ...
+//! }
+//! ```
+
+use std::fmt::Write as _;
+use std::io::{self, Write as _};
+use std::str::FromStr;
+
+use crate::info::CommandMeta;
+use crate::Commands;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Markdown,
+    Html,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            _ => Err(format!("unknown format \"{}\", expected `markdown` or `html`", s)),
+        }
+    }
+}
+
+/// Generates the reference of the commands of `C`.
+pub fn generate<C: Commands>(format: Format) -> String {
+    let commands = C::METADATA;
+
+    match format {
+        Format::Markdown => markdown(commands),
+        Format::Html => html(commands),
+    }
+}
+
+/// Writes the reference of the commands of `C`, as requested by `args`, which
+/// may contain `--format <markdown|html>` and `--output <path>`.
+///
+/// The reference is written in Markdown to the standard output by default.
+pub fn run<C: Commands>(args: impl IntoIterator<Item = String>) -> io::Result<()> {
+    let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidInput, msg);
+
+    let mut format = Format::Markdown;
+    let mut output = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg != "--format" && arg != "--output" {
+            return Err(invalid(format!("unknown flag \"{}\"", arg)));
+        }
+
+        let value =
+            args.next().ok_or_else(|| invalid(format!("expected a value for `{}`", arg)))?;
+
+        match &arg[..] {
+            "--format" => format = value.parse().map_err(invalid)?,
+            _ => output = Some(value),
+        }
+    }
+
+    let reference = generate::<C>(format);
+
+    match output {
+        Some(path) => std::fs::write(path, reference),
+        None => io::stdout().write_all(reference.as_bytes()),
+    }
+}
+
+/// Generates a Markdown reference of `commands`, with a section for each
+/// command, subcommand group and subcommand.
+pub fn markdown(commands: &[&CommandMeta]) -> String {
+    let mut out = String::from("# Commands\n");
+
+    for cmd in commands {
+        write_markdown(&mut out, cmd, cmd.name, 2);
+    }
+
+    out
+}
+
+fn write_markdown(out: &mut String, cmd: &CommandMeta, path: &str, level: usize) {
+    let _ = write!(
+        out,
+        "\n{} `/{}`\n\n{}\n",
+        "#".repeat(level),
+        path,
+        escape_markdown(cmd.description),
+    );
+
+    if let Some(permissions) = cmd.default_member_permissions {
+        let names = permissions.names.iter().map(|name| format!("`{}`", name)).collect::<Vec<_>>();
+        let _ = write!(out, "\nRequired permissions: {}.\n", names.join(", "));
+    }
+
+    if cmd.dm_permission == Some(false) {
+        out.push_str("\nNot available in direct messages.\n");
+    }
+
+    let options = cmd.options();
+    if !options.is_empty() {
+        out.push_str("\n| Option | Type | Required | Description |\n| --- | --- | --- | --- |\n");
+
+        for opt in options {
+            let _ = writeln!(
+                out,
+                "| `{}` | {} | {} | {} |",
+                opt.name,
+                opt.kind_name(),
+                if opt.required { "yes" } else { "no" },
+                escape_markdown(opt.description),
+            );
+        }
+    }
+
+    for sub in cmd.subcommands {
+        write_markdown(out, sub, &format!("{} {}", path, sub.name), (level + 1).min(6));
+    }
+}
+
+/// Escapes the pipes of `s`, which would otherwise end a cell of a table.
+fn escape_markdown(s: &str) -> String {
+    s.replace('|', "\\|")
+}
+
+/// Generates an HTML fragment referencing `commands`, with a section for
+/// each command, subcommand group and subcommand.
+pub fn html(commands: &[&CommandMeta]) -> String {
+    let mut out = String::new();
+
+    for cmd in commands {
+        write_html(&mut out, cmd, cmd.name, 2);
+    }
+
+    out
+}
+
+fn write_html(out: &mut String, cmd: &CommandMeta, path: &str, level: usize) {
+    let _ = writeln!(out, "<section id=\"{}\">", escape(&path.replace(' ', "-")));
+    let _ = writeln!(out, "<h{0}><code>/{1}</code></h{0}>", level, escape(path));
+    let _ = writeln!(out, "<p>{}</p>", escape(cmd.description));
+
+    if let Some(permissions) = cmd.default_member_permissions {
+        let names = permissions
+            .names
+            .iter()
+            .map(|name| format!("<code>{}</code>", escape(name)))
+            .collect::<Vec<_>>();
+        let _ = writeln!(out, "<p>Required permissions: {}.</p>", names.join(", "));
+    }
+
+    if cmd.dm_permission == Some(false) {
+        out.push_str("<p>Not available in direct messages.</p>\n");
+    }
+
+    let options = cmd.options();
+    if !options.is_empty() {
+        out.push_str("<table>\n<thead><tr><th>Option</th><th>Type</th><th>Required</th><th>Description</th></tr></thead>\n<tbody>\n");
+
+        for opt in options {
+            let _ = writeln!(
+                out,
+                "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape(opt.name),
+                opt.kind_name(),
+                if opt.required { "yes" } else { "no" },
+                escape(opt.description),
+            );
+        }
+
+        out.push_str("</tbody>\n</table>\n");
+    }
+
+    for sub in cmd.subcommands {
+        write_html(out, sub, &format!("{} {}", path, sub.name), (level + 1).min(6));
+    }
+
+    out.push_str("</section>\n");
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
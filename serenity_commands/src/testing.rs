@@ -0,0 +1,436 @@
+//! Utilities for constructing interactions in tests.
+//!
+//! Interactions are assembled as the JSON payloads Discord would send and are
+//! then deserialized by Serenity, so the values handed to the generated
+//! `parse` functions are identical to those received from the gateway.
+//!
+//! ```
+//! use serenity_commands::testing::{InteractionBuilder, SubCommandBuilder};
+//!
+//! let interaction = InteractionBuilder::command("config")
+//!     .subcommand(SubCommandBuilder::new("set").option("key", "prefix").option("value", "!"))
+//!     .build();
+//!
+//! assert_eq!(interaction.data.name, "config");
+//! assert_eq!(interaction.data.options[0].name, "set");
+//! ```
+
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use std::sync::Arc;
+
+use serde_json::{json, Map, Value};
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::cache::Cache;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::client::bridge::gateway::ShardMessenger;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::client::Context;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::futures::channel::mpsc;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::http::Http;
+#[cfg(serenity = "0_12")]
+use serenity::model::application::{CommandData, CommandInteraction, CommandOptionType};
+use serenity::model::channel::PartialChannel;
+use serenity::model::guild::Role;
+use serenity::model::id::{ChannelId, GuildId};
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::model::interactions::application_command::{
+    ApplicationCommandInteraction as CommandInteraction,
+    ApplicationCommandInteractionData as CommandData,
+    ApplicationCommandOptionType as CommandOptionType,
+};
+use serenity::model::user::User;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::prelude::{RwLock, TypeMap};
+
+use crate::model::InteractionOptions;
+
+/// Creates a context that is not connected to Discord, for running handlers.
+///
+/// Requests made through it fail, as it has no token. It is not available on
+/// Serenity 0.12, whose contexts need a running shard.
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+pub fn context() -> Context {
+    let (tx, _) = mpsc::unbounded();
+
+    #[cfg(serenity = "0_10")]
+    let http = Http::default();
+    #[cfg(serenity = "0_11")]
+    let http = Http::new("");
+
+    Context {
+        data: Arc::new(RwLock::new(TypeMap::new())),
+        shard: ShardMessenger::new(tx),
+        shard_id: 0,
+        http: Arc::new(http),
+        cache: Arc::new(Cache::default()),
+    }
+}
+
+/// Creates a user with the given id and name.
+pub fn user(id: u64, name: &str) -> User {
+    let mut user = User::default();
+    user.id = id.into();
+    user.name = name.to_string();
+    user
+}
+
+/// Creates a text channel with the given id and name.
+pub fn channel(id: u64, name: &str) -> PartialChannel {
+    serde_json::from_value(json!({
+        "id": id.to_string(),
+        "name": name,
+        "type": 0,
+        "permissions": null,
+    }))
+    .expect("failed to deserialize channel")
+}
+
+/// Creates a role in the given guild, with the given id and name.
+pub fn role(guild_id: u64, id: u64, name: &str) -> Role {
+    serde_json::from_value(json!({
+        "id": id.to_string(),
+        "guild_id": guild_id.to_string(),
+        "name": name,
+        "color": 0,
+        "colors": { "primary_color": 0 },
+        "hoist": false,
+        "managed": false,
+        "mentionable": false,
+        "permissions": "0",
+        "position": 0,
+    }))
+    .expect("failed to deserialize role")
+}
+
+/// A value for an option of a command.
+///
+/// Users, channels and roles are placed in the resolved data of the
+/// interaction, and referenced by their id in the option.
+#[derive(Debug, Clone)]
+pub enum OptionValue {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+    Number(f64),
+    User(User),
+    Channel(PartialChannel),
+    Role(Role),
+}
+
+impl OptionValue {
+    fn kind(&self) -> u8 {
+        let kind = match self {
+            Self::String(_) => CommandOptionType::String,
+            Self::Integer(_) => CommandOptionType::Integer,
+            Self::Boolean(_) => CommandOptionType::Boolean,
+            Self::Number(_) => CommandOptionType::Number,
+            Self::User(_) => CommandOptionType::User,
+            Self::Channel(_) => CommandOptionType::Channel,
+            Self::Role(_) => CommandOptionType::Role,
+        };
+
+        option_type(kind)
+    }
+}
+
+impl From<&str> for OptionValue {
+    fn from(s: &str) -> Self {
+        Self::String(s.to_string())
+    }
+}
+
+impl From<String> for OptionValue {
+    fn from(s: String) -> Self {
+        Self::String(s)
+    }
+}
+
+impl From<i32> for OptionValue {
+    fn from(n: i32) -> Self {
+        Self::Integer(n.into())
+    }
+}
+
+impl From<i64> for OptionValue {
+    fn from(n: i64) -> Self {
+        Self::Integer(n)
+    }
+}
+
+impl From<bool> for OptionValue {
+    fn from(b: bool) -> Self {
+        Self::Boolean(b)
+    }
+}
+
+impl From<f64> for OptionValue {
+    fn from(n: f64) -> Self {
+        Self::Number(n)
+    }
+}
+
+impl From<User> for OptionValue {
+    fn from(user: User) -> Self {
+        Self::User(user)
+    }
+}
+
+impl From<PartialChannel> for OptionValue {
+    fn from(channel: PartialChannel) -> Self {
+        Self::Channel(channel)
+    }
+}
+
+impl From<Role> for OptionValue {
+    fn from(role: Role) -> Self {
+        Self::Role(role)
+    }
+}
+
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+fn option_type(kind: CommandOptionType) -> u8 {
+    kind as u8
+}
+
+#[cfg(serenity = "0_12")]
+fn option_type(kind: CommandOptionType) -> u8 {
+    kind.into()
+}
+
+#[derive(Debug, Clone, Default)]
+struct Resolved {
+    users: Map<String, Value>,
+    channels: Map<String, Value>,
+    roles: Map<String, Value>,
+}
+
+impl Resolved {
+    fn option(&mut self, name: &str, value: OptionValue) -> Value {
+        let kind = value.kind();
+
+        let value = match value {
+            OptionValue::String(s) => json!(s),
+            OptionValue::Integer(n) => json!(n),
+            OptionValue::Boolean(b) => json!(b),
+            OptionValue::Number(n) => json!(n),
+            OptionValue::User(user) => insert(&mut self.users, user.id.to_string(), &user),
+            OptionValue::Channel(channel) => {
+                insert(&mut self.channels, channel.id.to_string(), &channel)
+            },
+            OptionValue::Role(role) => {
+                let id = insert(&mut self.roles, role.id.to_string(), &role);
+
+                // Serenity serializes absent role tags as `null`s, which it
+                // then refuses to deserialize.
+                if let Some(Value::Object(role)) = self.roles.get_mut(&role.id.to_string()) {
+                    role.remove("tags");
+                }
+
+                id
+            },
+        };
+
+        json!({
+            "name": name,
+            "type": kind,
+            "value": value,
+        })
+    }
+
+    fn merge(&mut self, other: Resolved) {
+        self.users.extend(other.users);
+        self.channels.extend(other.channels);
+        self.roles.extend(other.roles);
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "users": self.users,
+            "channels": self.channels,
+            "roles": self.roles,
+        })
+    }
+}
+
+fn insert<T: serde::Serialize>(map: &mut Map<String, Value>, id: String, value: &T) -> Value {
+    map.insert(id.clone(), serde_json::to_value(value).expect("failed to serialize value"));
+
+    Value::String(id)
+}
+
+/// Builds a subcommand option, to be nested in a command or a subcommand
+/// group.
+#[derive(Debug, Clone)]
+pub struct SubCommandBuilder {
+    name: String,
+    options: Vec<Value>,
+    resolved: Resolved,
+}
+
+impl SubCommandBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            options: Vec::new(),
+            resolved: Resolved::default(),
+        }
+    }
+
+    pub fn option(mut self, name: &str, value: impl Into<OptionValue>) -> Self {
+        self.options.push(self.resolved.option(name, value.into()));
+        self
+    }
+
+    fn into_json(self) -> (Value, Resolved) {
+        let option = json!({
+            "name": self.name,
+            "type": option_type(CommandOptionType::SubCommand),
+            "options": self.options,
+        });
+
+        (option, self.resolved)
+    }
+}
+
+/// Builds a subcommand group option, to be nested in a command.
+#[derive(Debug, Clone)]
+pub struct GroupBuilder {
+    name: String,
+    options: Vec<Value>,
+    resolved: Resolved,
+}
+
+impl GroupBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            options: Vec::new(),
+            resolved: Resolved::default(),
+        }
+    }
+
+    pub fn subcommand(mut self, subcommand: SubCommandBuilder) -> Self {
+        let (option, resolved) = subcommand.into_json();
+
+        self.options.push(option);
+        self.resolved.merge(resolved);
+        self
+    }
+
+    fn into_json(self) -> (Value, Resolved) {
+        let option = json!({
+            "name": self.name,
+            "type": option_type(CommandOptionType::SubCommandGroup),
+            "options": self.options,
+        });
+
+        (option, self.resolved)
+    }
+}
+
+/// Builds an application command interaction, as if a user had invoked the
+/// command.
+#[derive(Debug, Clone)]
+pub struct InteractionBuilder {
+    name: String,
+    options: Vec<Value>,
+    resolved: Resolved,
+    guild_id: Option<GuildId>,
+    channel_id: ChannelId,
+    user: User,
+}
+
+impl InteractionBuilder {
+    pub fn command(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            options: Vec::new(),
+            resolved: Resolved::default(),
+            guild_id: None,
+            channel_id: ChannelId::from(1),
+            user: User::default(),
+        }
+    }
+
+    pub fn option(mut self, name: &str, value: impl Into<OptionValue>) -> Self {
+        self.options.push(self.resolved.option(name, value.into()));
+        self
+    }
+
+    pub fn subcommand(mut self, subcommand: SubCommandBuilder) -> Self {
+        let (option, resolved) = subcommand.into_json();
+
+        self.options.push(option);
+        self.resolved.merge(resolved);
+        self
+    }
+
+    pub fn group(mut self, group: GroupBuilder) -> Self {
+        let (option, resolved) = group.into_json();
+
+        self.options.push(option);
+        self.resolved.merge(resolved);
+        self
+    }
+
+    /// Sets the guild the command is invoked in. By default, it is invoked
+    /// in a direct message.
+    pub fn guild(mut self, guild_id: impl Into<GuildId>) -> Self {
+        self.guild_id = Some(guild_id.into());
+        self
+    }
+
+    pub fn channel(mut self, channel_id: impl Into<ChannelId>) -> Self {
+        self.channel_id = channel_id.into();
+        self
+    }
+
+    /// Sets the user invoking the command.
+    pub fn user(mut self, user: User) -> Self {
+        self.user = user;
+        self
+    }
+
+    /// Returns the raw payload of the interaction.
+    pub fn to_json(&self) -> Value {
+        let mut payload = json!({
+            "id": "1",
+            "application_id": "1",
+            "type": 2,
+            "token": "token",
+            "version": 1,
+            "channel_id": self.channel_id.to_string(),
+            "locale": "en-US",
+            "entitlements": [],
+            "attachment_size_limit": 0,
+            "user": serde_json::to_value(&self.user).expect("failed to serialize user"),
+            "data": {
+                "id": "1",
+                "name": self.name,
+                "type": 1,
+                "options": self.options,
+                "resolved": self.resolved.to_json(),
+            },
+        });
+
+        if let Some(guild_id) = self.guild_id {
+            payload["guild_id"] = Value::String(guild_id.to_string());
+        }
+
+        payload
+    }
+
+    pub fn build(&self) -> CommandInteraction {
+        serde_json::from_value(self.to_json()).expect("failed to deserialize interaction")
+    }
+
+    pub fn build_data(&self) -> CommandData {
+        self.build().data
+    }
+
+    pub fn build_options(&self) -> InteractionOptions {
+        InteractionOptions::try_from(&self.build_data()).expect("failed to convert options")
+    }
+}
@@ -0,0 +1,105 @@
+//! Spans and events emitted when parsing, registering and dispatching
+//! commands, enabled by the `tracing` feature.
+//!
+//! The spans carry the path of the invoked command, such as
+//! `config prefix set`, and the ids of the guild, channel and user it was
+//! invoked by. The generated code calls these functions, which are public so
+//! that it can.
+
+#[cfg(not(twilight))]
+use std::future::Future;
+
+#[cfg(serenity = "0_12")]
+use serenity::model::application::CommandInteraction;
+#[cfg(not(twilight))]
+use serenity::model::id::GuildId;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::model::interactions::application_command::ApplicationCommandInteraction as CommandInteraction;
+use tracing::{debug, debug_span, warn, Span};
+#[cfg(not(twilight))]
+use tracing::{error, info, info_span, Instrument};
+#[cfg(twilight)]
+use twilight_model::application::interaction::application_command::CommandData;
+
+use crate::error::ParseError;
+use crate::info;
+
+/// Parses `interaction` with `parse` in a span, and emits an event with the
+/// error if it fails.
+#[cfg(not(twilight))]
+pub fn parse<T>(
+    interaction: CommandInteraction,
+    parse: impl FnOnce(CommandInteraction) -> Result<T, ParseError>,
+) -> Result<T, ParseError> {
+    let span = debug_span!(
+        "parse_command",
+        path = %info::path(&interaction.data),
+        guild_id = interaction.guild_id.map(u64::from),
+        channel_id = u64::from(interaction.channel_id),
+        user_id = u64::from(interaction.user.id),
+    );
+
+    parsed(span, || parse(interaction))
+}
+
+/// Parses `data` with `parse` in a span, and emits an event with the error if
+/// it fails.
+#[cfg(twilight)]
+pub fn parse<T>(
+    data: CommandData,
+    parse: impl FnOnce(CommandData) -> Result<T, ParseError>,
+) -> Result<T, ParseError> {
+    let span = debug_span!(
+        "parse_command",
+        path = %info::path(&data),
+        guild_id = data.guild_id.map(|id| id.get()),
+    );
+
+    parsed(span, || parse(data))
+}
+
+fn parsed<T>(span: Span, parse: impl FnOnce() -> Result<T, ParseError>) -> Result<T, ParseError> {
+    let _span = span.enter();
+
+    let result = parse();
+
+    match &result {
+        Ok(_) => debug!("parsed command"),
+        Err(e) => warn!(error = %e, details = ?e, "failed to parse command"),
+    }
+
+    result
+}
+
+/// Awaits the registration of commands, globally or in the guild with
+/// `guild_id`, in a span, and emits an event with its outcome.
+#[cfg(not(twilight))]
+pub async fn register<T>(
+    guild_id: Option<GuildId>,
+    registration: impl Future<Output = serenity::Result<Vec<T>>>,
+) -> serenity::Result<Vec<T>> {
+    let span = info_span!("register_commands", guild_id = guild_id.map(u64::from));
+
+    let result = registration.instrument(span.clone()).await;
+
+    let _span = span.enter();
+
+    match &result {
+        Ok(commands) => info!(count = commands.len(), "registered commands"),
+        Err(e) => error!(error = %e, "failed to register commands"),
+    }
+
+    result
+}
+
+/// Returns the span in which `interaction` is dispatched.
+#[cfg(not(twilight))]
+pub(crate) fn dispatch_span(interaction: &CommandInteraction) -> Span {
+    info_span!(
+        "dispatch_command",
+        path = %info::path(&interaction.data),
+        guild_id = interaction.guild_id.map(u64::from),
+        channel_id = u64::from(interaction.channel_id),
+        user_id = u64::from(interaction.user.id),
+    )
+}
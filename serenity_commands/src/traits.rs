@@ -0,0 +1,154 @@
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::builder::{
+    CreateApplicationCommand,
+    CreateApplicationCommandOption as CreateCommandOption,
+};
+#[cfg(serenity = "0_12")]
+use serenity::builder::{CreateCommand, CreateCommandOption};
+#[cfg(serenity = "0_12")]
+use serenity::model::application::{CommandData, CommandInteraction};
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity::model::interactions::application_command::{
+    ApplicationCommandInteraction as CommandInteraction,
+    ApplicationCommandInteractionData as CommandData,
+};
+#[cfg(twilight)]
+use twilight_model::application::command::{
+    Command as CreateCommand,
+    CommandOption as CreateCommandOption,
+};
+#[cfg(twilight)]
+use twilight_model::application::interaction::application_command::CommandData;
+
+use crate::error::ParseError;
+use crate::info::{CommandMeta, OptionEntry};
+use crate::message::Arguments;
+use crate::model::{CommandDefinition, InteractionOption};
+
+/// A set of application commands that can be parsed out of an interaction.
+///
+/// This is implemented by `#[derive(Commands)]`.
+pub trait Commands: Sized {
+    /// The structure of each command of the set.
+    const METADATA: &'static [&'static CommandMeta];
+
+    #[cfg(not(twilight))]
+    fn parse(interaction: CommandInteraction) -> Result<Self, ParseError>;
+
+    /// Parses the data of an application command interaction, which Twilight
+    /// keeps apart from the interaction.
+    #[cfg(twilight)]
+    fn parse(data: CommandData) -> Result<Self, ParseError>;
+
+    /// Returns the definition of each command of the set.
+    fn definitions() -> Vec<CommandDefinition>;
+
+    fn metadata() -> impl Iterator<Item = &'static CommandMeta> {
+        Self::METADATA.iter().copied()
+    }
+}
+
+/// An application command.
+///
+/// This is implemented by `#[derive(Command)]`.
+///
+/// With Serenity 0.12, whose builders are values, and with Twilight, the
+/// functions registering commands and options are replaced by functions
+/// creating them, such as `create_command`.
+pub trait Command: Sized {
+    const METADATA: &'static CommandMeta;
+
+    fn name() -> &'static str;
+
+    #[cfg(any(serenity = "0_10", serenity = "0_11"))]
+    fn register_command(cmd: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand;
+
+    #[cfg(any(serenity = "0_12", twilight))]
+    fn create_command() -> CreateCommand;
+
+    fn parse_command(data: CommandData) -> Result<Self, ParseError>;
+
+    /// Returns the definition of the command, which its registration is
+    /// created from.
+    fn definition() -> CommandDefinition;
+}
+
+/// A subcommand of a command or a subcommand group.
+///
+/// This is implemented by `#[derive(Command)]` on structs.
+pub trait SubCommand: Sized {
+    const METADATA: &'static CommandMeta;
+
+    fn name() -> &'static str;
+
+    #[cfg(any(serenity = "0_10", serenity = "0_11"))]
+    fn register_subcommand(opt: &mut CreateCommandOption) -> &mut CreateCommandOption;
+
+    #[cfg(any(serenity = "0_12", twilight))]
+    fn create_subcommand() -> CreateCommandOption;
+
+    fn parse_subcommand(option: InteractionOption) -> Result<Self, ParseError>;
+
+    fn to_subcommand_option(&self) -> InteractionOption;
+}
+
+/// A subcommand group of a command.
+///
+/// This is implemented by `#[derive(Group)]`.
+pub trait Group: Sized {
+    const METADATA: &'static CommandMeta;
+
+    fn name() -> &'static str;
+
+    #[cfg(any(serenity = "0_10", serenity = "0_11"))]
+    fn register_subcommand_group(opt: &mut CreateCommandOption) -> &mut CreateCommandOption;
+
+    #[cfg(any(serenity = "0_12", twilight))]
+    fn create_subcommand_group() -> CreateCommandOption;
+
+    fn parse_subcommand_group(option: InteractionOption) -> Result<Self, ParseError>;
+
+    fn to_subcommand_group_option(&self) -> InteractionOption;
+}
+
+/// Either a subcommand or a subcommand group, which lets commands hold
+/// them without being told which one they are.
+///
+/// This is implemented by `#[derive(Command)]` on structs and by
+/// `#[derive(Group)]`.
+pub trait SubCommandOption: Sized {
+    const METADATA: &'static CommandMeta;
+
+    fn name() -> &'static str;
+
+    #[cfg(any(serenity = "0_10", serenity = "0_11"))]
+    fn register_option(opt: &mut CreateCommandOption) -> &mut CreateCommandOption;
+
+    #[cfg(any(serenity = "0_12", twilight))]
+    fn create_option() -> CreateCommandOption;
+
+    fn parse_option(option: InteractionOption) -> Result<Self, ParseError>;
+
+    fn to_option(&self) -> InteractionOption;
+}
+
+/// A set of options that can be shared between commands with
+/// `#[option(flatten)]`.
+///
+/// This is implemented by `#[derive(Options)]`.
+pub trait Options: Sized {
+    /// The number of options in the set, including flattened ones.
+    const COUNT: usize;
+
+    /// The structure of the options of the set.
+    const METADATA: &'static [OptionEntry];
+
+    fn create_options() -> Vec<CreateCommandOption>;
+
+    /// Parses the options of the set out of `options`, leaving the others.
+    fn parse_options(options: &mut Vec<InteractionOption>) -> Result<Self, ParseError>;
+
+    fn to_options(&self) -> Vec<InteractionOption>;
+
+    fn parse_arguments(args: &mut Arguments) -> Result<Self, ParseError>;
+}
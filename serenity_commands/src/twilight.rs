@@ -0,0 +1,83 @@
+//! Construction of Twilight's commands, used to convert definitions when
+//! targeting Twilight.
+//!
+//! Twilight has no builders, so commands and options are created with their
+//! optional fields unset, and options are then pushed into them.
+
+use serde::Deserialize;
+use serde_json::Value;
+use twilight_model::application::command::{
+    Command,
+    CommandOption,
+    CommandOptionType,
+    CommandType,
+};
+use twilight_model::application::interaction::application_command::CommandData;
+use twilight_model::application::interaction::{Interaction, InteractionData};
+use twilight_model::id::Id;
+
+use crate::error::ParseError;
+
+/// Creates a chat input command without options.
+#[allow(deprecated)]
+pub fn command(name: &str, description: &str) -> Command {
+    Command {
+        application_id: None,
+        contexts: None,
+        default_member_permissions: None,
+        dm_permission: None,
+        description: description.to_string(),
+        description_localizations: None,
+        guild_id: None,
+        id: None,
+        integration_types: None,
+        kind: CommandType::ChatInput,
+        name: name.to_string(),
+        name_localizations: None,
+        nsfw: None,
+        options: Vec::new(),
+        version: Id::new(1),
+    }
+}
+
+/// Creates an optional option of type `kind`, without sub-options.
+pub fn option(kind: CommandOptionType, name: &str, description: &str) -> CommandOption {
+    CommandOption {
+        autocomplete: None,
+        channel_types: None,
+        choices: None,
+        description: description.to_string(),
+        description_localizations: None,
+        kind,
+        max_length: None,
+        max_value: None,
+        min_length: None,
+        min_value: None,
+        name: name.to_string(),
+        name_localizations: None,
+        options: None,
+        required: None,
+    }
+}
+
+pub fn add_option(cmd: &mut Command, option: CommandOption) {
+    cmd.options.push(option);
+}
+
+pub fn add_sub_option(opt: &mut CommandOption, option: CommandOption) {
+    opt.options.get_or_insert_with(Vec::new).push(option);
+}
+
+/// Extracts the data of an application command out of the payload of an
+/// interaction.
+pub fn command_data(interaction: &Value) -> Result<CommandData, ParseError> {
+    let interaction = Interaction::deserialize(interaction)
+        .map_err(|e| ParseError::InvalidPayload(e.to_string()))?;
+
+    match interaction.data {
+        Some(InteractionData::ApplicationCommand(data)) => Ok(*data),
+        _ => Err(ParseError::InvalidPayload(
+            "expected an application command interaction".to_string(),
+        )),
+    }
+}
@@ -0,0 +1,61 @@
+//! Commands shared by the tests, which add the ones specific to what they
+//! test.
+#![allow(dead_code)]
+
+use serenity_commands::macros::{Command, Group};
+#[cfg(serenity = "0_12")]
+use serenity_commands::serenity::model::id::UserId as User;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity_commands::serenity::model::user::User;
+#[cfg(twilight)]
+use serenity_commands::twilight_model::id::{marker::UserMarker, Id};
+
+#[cfg(twilight)]
+type User = Id<UserMarker>;
+
+/// Ban a user.
+#[derive(Debug, PartialEq, Command)]
+#[command(prefix)]
+pub struct Ban {
+    /// The user to ban.
+    #[option(user)]
+    pub user: User,
+    /// The reason for the ban.
+    #[option(string)]
+    pub reason: Option<String>,
+    /// Days of messages to delete.
+    #[option(integer, name = "delete-days")]
+    pub days: Option<i64>,
+}
+
+/// Set a configuration value.
+#[derive(Debug, PartialEq, Command)]
+#[command(prefix)]
+pub struct Set {
+    /// The key to set.
+    #[option(string)]
+    pub key: String,
+}
+
+/// Reset the configuration.
+#[derive(Debug, PartialEq, Command)]
+#[command(prefix)]
+pub struct Reset;
+
+/// Manage the prefix.
+#[derive(Debug, PartialEq, Group)]
+#[group(prefix)]
+pub enum Prefix {
+    Set(Set),
+    Reset(Reset),
+}
+
+/// Configure the bot.
+#[derive(Debug, PartialEq, Command)]
+#[command(prefix, default_member_permissions = "MANAGE_GUILD", dm_permission = false)]
+pub enum Config {
+    #[option(subcommand)]
+    Reset(Reset),
+    #[option(group)]
+    Prefix(Prefix),
+}
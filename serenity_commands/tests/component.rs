@@ -0,0 +1,169 @@
+#![cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serde_json::{json, Value};
+use serenity_commands::error::ParseError;
+use serenity_commands::macros::Component;
+use serenity_commands::serenity::builder::{CreateButton, CreateSelectMenu};
+#[cfg(serenity = "0_11")]
+use serenity_commands::serenity::json::hashmap_to_json_map;
+use serenity_commands::serenity::model::interactions::message_component::MessageComponentInteractionData;
+#[cfg(serenity = "0_10")]
+use serenity_commands::serenity::utils::hashmap_to_json_map;
+
+#[derive(Debug, PartialEq, Component)]
+enum Poll {
+    #[component(id = "vote", label = "Vote", style = "primary")]
+    Vote { poll: u64, option: String },
+    #[component(id = "close", label = "Close", style = "danger")]
+    Close(u64),
+    #[component(id = "refresh")]
+    Refresh,
+    #[component(id = "rename")]
+    Rename { from: String, to: String },
+    #[component(id = "pick", placeholder = "Pick your options")]
+    Pick {
+        poll: u64,
+        #[component(values)]
+        options: Vec<String>,
+    },
+}
+
+fn data(custom_id: &str, values: &[&str]) -> MessageComponentInteractionData {
+    serde_json::from_value(json!({
+        "custom_id": custom_id,
+        "component_type": if values.is_empty() { 2 } else { 3 },
+        "values": values,
+    }))
+    .unwrap()
+}
+
+fn round_trip(component: Poll, values: &[&str]) {
+    let custom_id = component.custom_id().unwrap();
+
+    assert_eq!(Poll::parse_data(data(&custom_id, values)).unwrap(), component);
+}
+
+#[test]
+fn encodes_fields_in_custom_ids() {
+    let vote = Poll::Vote {
+        poll: 1,
+        option: "yes".to_string(),
+    };
+
+    assert_eq!(vote.custom_id().unwrap(), "vote:1:yes");
+    assert_eq!(Poll::Close(2).custom_id().unwrap(), "close:2");
+    assert_eq!(Poll::Refresh.custom_id().unwrap(), "refresh");
+}
+
+#[test]
+fn escapes_separators_in_fields() {
+    let rename = Poll::Rename {
+        from: "a:b".to_string(),
+        to: "50%".to_string(),
+    };
+
+    assert_eq!(rename.custom_id().unwrap(), "rename:a%3Ab:50%25");
+    round_trip(rename, &[]);
+
+    round_trip(
+        Poll::Rename {
+            from: "%3A:".to_string(),
+            to: ":".to_string(),
+        },
+        &[],
+    );
+}
+
+#[test]
+fn rejects_long_custom_ids() {
+    let vote = Poll::Vote {
+        poll: 1,
+        option: "a".repeat(93),
+    };
+
+    assert_eq!(vote.custom_id().unwrap().len(), 100);
+
+    let vote = Poll::Vote {
+        poll: 1,
+        option: "a".repeat(94),
+    };
+
+    assert!(matches!(vote.custom_id(), Err(ParseError::InvalidCustomId(_))));
+    assert!(vote.create_button(&mut CreateButton::default()).is_err());
+}
+
+#[test]
+fn round_trips_custom_ids() {
+    round_trip(
+        Poll::Vote {
+            poll: 1,
+            option: "yes: definitely".to_string(),
+        },
+        &[],
+    );
+    round_trip(Poll::Close(2), &[]);
+    round_trip(Poll::Refresh, &[]);
+}
+
+#[test]
+fn parses_select_menu_values() {
+    round_trip(
+        Poll::Pick {
+            poll: 3,
+            options: vec!["a".to_string(), "b".to_string()],
+        },
+        &["a", "b"],
+    );
+}
+
+#[test]
+fn rejects_unknown_components() {
+    assert!(matches!(
+        Poll::parse_data(data("delete:1", &[])),
+        Err(ParseError::UnknownComponent(id)) if id == "delete"
+    ));
+}
+
+#[test]
+fn rejects_invalid_custom_ids() {
+    for custom_id in ["close", "close:x", "vote:1", "refresh:1"] {
+        assert!(
+            matches!(Poll::parse_data(data(custom_id, &[])), Err(ParseError::InvalidCustomId(_))),
+            "{}",
+            custom_id
+        );
+    }
+}
+
+#[test]
+fn builds_buttons() {
+    let mut button = CreateButton::default();
+    Poll::Close(2).create_button(&mut button).unwrap();
+
+    assert_eq!(
+        Value::Object(hashmap_to_json_map(button.0)),
+        json!({
+            "custom_id": "close:2",
+            "label": "Close",
+            "style": 4,
+        })
+    );
+}
+
+#[test]
+fn builds_select_menus() {
+    let mut menu = CreateSelectMenu::default();
+    Poll::Pick {
+        poll: 3,
+        options: Vec::new(),
+    }
+    .create_select_menu(&mut menu)
+    .unwrap();
+
+    assert_eq!(
+        Value::Object(hashmap_to_json_map(menu.0)),
+        json!({
+            "custom_id": "pick:3",
+            "placeholder": "Pick your options",
+        })
+    );
+}
@@ -0,0 +1,213 @@
+#![cfg(any(serenity = "0_10", serenity = "0_11"))]
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use serenity_commands::context::CommandContext;
+use serenity_commands::dispatch::Dispatcher;
+use serenity_commands::macros::Commands;
+use serenity_commands::serenity::client::Context;
+use serenity_commands::serenity::http::HttpBuilder;
+use serenity_commands::testing::{self, InteractionBuilder};
+
+/// Ping the bot slowly.
+#[serenity_commands::command]
+async fn ping(ctx: CommandContext<'_>) {
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    ctx.reply("pong").await.unwrap();
+}
+
+#[derive(Debug, Commands)]
+#[commands(dispatch)]
+enum AllCommands {
+    Ping(Ping),
+}
+
+/// A server standing in for Discord, which records the responses it is sent
+/// as `respond`, `defer`, `edit` or `followup`.
+struct Discord {
+    url: String,
+    requests: Arc<Mutex<Vec<&'static str>>>,
+}
+
+impl Discord {
+    fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&requests);
+
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                Self::handle(stream, &recorded);
+            }
+        });
+
+        Self {
+            url,
+            requests,
+        }
+    }
+
+    fn handle(mut stream: TcpStream, requests: &Mutex<Vec<&'static str>>) {
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let path = line.split(' ').nth(1).unwrap_or_default().to_string();
+
+        let mut length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+
+            if header.trim().is_empty() {
+                break;
+            }
+
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse().unwrap();
+                }
+            }
+        }
+
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+
+        let request = if path.ends_with("/callback") {
+            let body: Value = serde_json::from_slice(&body).unwrap();
+
+            if body["type"] == 5 {
+                "defer"
+            } else {
+                "respond"
+            }
+        } else if path.ends_with("/messages/@original") {
+            "edit"
+        } else {
+            "followup"
+        };
+
+        requests.lock().unwrap().push(request);
+
+        let response = if request == "respond" || request == "defer" {
+            "HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n".to_string()
+        } else {
+            let message = message().to_string();
+
+            format!(
+                "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                message.len(),
+                message,
+            )
+        };
+
+        stream.write_all(response.as_bytes()).unwrap();
+    }
+
+    /// Creates a context sending its requests to the server.
+    async fn context(&self) -> Context {
+        let builder = HttpBuilder::new("token")
+            .application_id(1)
+            .proxy(self.url.as_str())
+            .unwrap()
+            .ratelimiter_disabled(true);
+
+        #[cfg(serenity = "0_10")]
+        let http = builder.await.unwrap();
+        #[cfg(serenity = "0_11")]
+        let http = builder.build();
+
+        let mut ctx = testing::context();
+        ctx.http = Arc::new(http);
+        ctx
+    }
+
+    fn requests(&self) -> Vec<&'static str> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// The message Discord returns for edits and followups.
+fn message() -> Value {
+    json!({
+        "id": "1",
+        "channel_id": "1",
+        "author": testing::user(1, "bot"),
+        "content": "",
+        "timestamp": "2021-01-01T00:00:00+00:00",
+        "edited_timestamp": null,
+        "tts": false,
+        "mention_everyone": false,
+        "mentions": [],
+        "mention_roles": [],
+        "attachments": [],
+        "embeds": [],
+        "pinned": false,
+        "type": 0,
+    })
+}
+
+#[tokio::test]
+async fn follows_up_after_replies() {
+    let discord = Discord::start();
+    let ctx = discord.context().await;
+    let interaction = InteractionBuilder::command("ping").build();
+
+    let ctx = CommandContext::new(&ctx, &interaction, ());
+
+    ctx.reply("pong").await.unwrap();
+    ctx.followup("pong again").await.unwrap();
+
+    assert_eq!(discord.requests(), ["respond", "followup"]);
+}
+
+#[tokio::test]
+async fn defers_followups_before_the_response() {
+    let discord = Discord::start();
+    let ctx = discord.context().await;
+    let interaction = InteractionBuilder::command("ping").build();
+
+    let ctx = CommandContext::new(&ctx, &interaction, ());
+
+    ctx.followup("pong").await.unwrap();
+    ctx.reply("pong again").await.unwrap();
+
+    assert_eq!(discord.requests(), ["defer", "followup", "followup"]);
+}
+
+#[tokio::test]
+async fn replies_after_edits_with_followups() {
+    let discord = Discord::start();
+    let ctx = discord.context().await;
+    let interaction = InteractionBuilder::command("ping").build();
+
+    let ctx = CommandContext::new(&ctx, &interaction, ());
+
+    ctx.edit_original("pong").await.unwrap();
+    ctx.reply("pong again").await.unwrap();
+
+    assert_eq!(discord.requests(), ["defer", "edit", "followup"]);
+
+    ctx.edit_original("pong once more").await.unwrap();
+
+    assert_eq!(discord.requests(), ["defer", "edit", "followup", "edit"]);
+}
+
+#[tokio::test]
+async fn edits_deferred_responses_of_slow_commands() {
+    let discord = Discord::start();
+    let ctx = discord.context().await;
+    let interaction = InteractionBuilder::command("ping").build();
+
+    let dispatcher = Dispatcher::new().auto_defer(Duration::from_millis(10));
+    dispatcher.dispatch::<AllCommands>(&ctx, &interaction).await.unwrap();
+
+    assert_eq!(discord.requests(), ["defer", "edit"]);
+}
@@ -0,0 +1,238 @@
+#![cfg(any(serenity = "0_10", serenity = "0_11"))]
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde_json::json;
+use serenity_commands::context::CommandContext;
+use serenity_commands::dispatch::{dispatch, Dispatcher, Handler, Invocation, Middleware};
+use serenity_commands::error::ParseError;
+use serenity_commands::macros::Commands;
+use serenity_commands::serenity::builder::CreateApplicationCommand;
+use serenity_commands::serenity::client::Context;
+#[cfg(serenity = "0_11")]
+use serenity_commands::serenity::json::hashmap_to_json_map;
+use serenity_commands::serenity::model::interactions::application_command::ApplicationCommandInteraction;
+use serenity_commands::serenity::model::user::User;
+#[cfg(serenity = "0_10")]
+use serenity_commands::serenity::utils::hashmap_to_json_map;
+use serenity_commands::testing::{self, InteractionBuilder};
+
+/// Ping the bot.
+#[serenity_commands::command]
+async fn ping(
+    _ctx: &Context,
+    _interaction: &ApplicationCommandInteraction,
+    /// How many times to ping.
+    times: Option<i64>,
+) -> String {
+    "pong ".repeat(times.unwrap_or(1) as usize).trim_end().to_string()
+}
+
+/// Greet a user.
+#[serenity_commands::command(name = "greet")]
+async fn greet_user(
+    _ctx: &Context,
+    interaction: &ApplicationCommandInteraction,
+    /// The user to greet.
+    user: User,
+    /// The greeting to use.
+    #[option(name = "message", string)]
+    greeting: String,
+) -> String {
+    format!("{}, {}! (from {})", greeting, user.name, interaction.user.name)
+}
+
+/// Echo a message.
+#[serenity_commands::command]
+async fn echo(
+    ctx: CommandContext<'_>,
+    /// The text to echo.
+    text: String,
+) -> String {
+    format!("{} (from {})", text, ctx.interaction.user.name)
+}
+
+/// Generate a report.
+#[serenity_commands::command(defer(ephemeral))]
+async fn report(ctx: CommandContext<'_>) -> String {
+    let _ = ctx.reply("The report is ready.").await;
+
+    "done".to_string()
+}
+
+#[derive(Debug, Commands)]
+#[commands(dispatch)]
+enum AllCommands {
+    Ping(Ping),
+    GreetUser(GreetUser),
+    Echo(Echo),
+    Report(Report),
+}
+
+/// Records the hooks it runs.
+struct Recorder {
+    name: &'static str,
+    events: Arc<Mutex<Vec<String>>>,
+}
+
+#[serenity_commands::serenity::async_trait]
+impl Middleware<String> for Recorder {
+    async fn before(&self, invocation: &Invocation<'_>) -> Option<String> {
+        let description = invocation.meta.map_or("", |m| m.description);
+
+        self.events
+            .lock()
+            .unwrap()
+            .push(format!("{} before {}: {}", self.name, invocation.path, description));
+
+        None
+    }
+
+    async fn after(&self, invocation: &Invocation<'_>, output: &String, _elapsed: Duration) {
+        self.events
+            .lock()
+            .unwrap()
+            .push(format!("{} after {}: {}", self.name, invocation.path, output));
+    }
+}
+
+/// Stops `greet` from running.
+struct NoGreetings;
+
+#[serenity_commands::serenity::async_trait]
+impl Middleware<String> for NoGreetings {
+    async fn before(&self, invocation: &Invocation<'_>) -> Option<String> {
+        (invocation.path == "greet").then(|| "no greetings".to_string())
+    }
+}
+
+#[test]
+fn generates_command_structs() {
+    let mut cmd = CreateApplicationCommand::default();
+    GreetUser::register_command(&mut cmd);
+
+    assert_eq!(
+        serde_json::Value::Object(hashmap_to_json_map(cmd.0)),
+        json!({
+            "name": "greet",
+            "description": "Greet a user.",
+            "options": [
+                {
+                    "type": 6,
+                    "name": "user",
+                    "description": "The user to greet.",
+                    "required": true,
+                },
+                {
+                    "type": 3,
+                    "name": "message",
+                    "description": "The greeting to use.",
+                    "required": true,
+                },
+            ],
+        })
+    );
+}
+
+#[tokio::test]
+async fn runs_commands() {
+    let ctx = testing::context();
+    let interaction = InteractionBuilder::command("ping").option("times", 3).build();
+
+    let ping = Ping::parse_command(interaction.data.clone()).unwrap();
+    assert_eq!(ping.run(&ctx, &interaction).await, "pong pong pong");
+
+    // The function itself can still be called directly.
+    assert_eq!(self::ping(&ctx, &interaction, None).await, "pong");
+}
+
+#[tokio::test]
+async fn dispatches_commands() {
+    let ctx = testing::context();
+    let interaction = InteractionBuilder::command("greet")
+        .user(testing::user(1, "alice"))
+        .option("user", testing::user(2, "bob"))
+        .option("message", "Hello")
+        .build();
+
+    assert_eq!(
+        dispatch::<AllCommands>(&ctx, &interaction).await.unwrap(),
+        "Hello, bob! (from alice)"
+    );
+
+    let interaction = InteractionBuilder::command("kick").build();
+
+    assert!(matches!(
+        dispatch::<AllCommands>(&ctx, &interaction).await,
+        Err(ParseError::UnknownCommand(name)) if name == "kick"
+    ));
+}
+
+#[tokio::test]
+async fn passes_command_contexts() {
+    let ctx = testing::context();
+    let interaction = InteractionBuilder::command("echo")
+        .user(testing::user(1, "alice"))
+        .option("text", "hi")
+        .build();
+
+    assert_eq!(dispatch::<AllCommands>(&ctx, &interaction).await.unwrap(), "hi (from alice)");
+
+    let parsed = CommandContext::parse::<AllCommands>(&ctx, &interaction).unwrap();
+
+    assert!(matches!(parsed.command, AllCommands::Echo(Echo { text }) if text == "hi"));
+    assert_eq!(parsed.interaction.data.name, "echo");
+}
+
+#[tokio::test]
+async fn dispatches_without_deferring_fast_commands() {
+    let ctx = testing::context();
+    let interaction = InteractionBuilder::command("ping").build();
+
+    let dispatcher = Dispatcher::new().auto_defer(Duration::from_secs(2)).ephemeral(true);
+
+    assert_eq!(dispatcher.dispatch::<AllCommands>(&ctx, &interaction).await.unwrap(), "pong");
+}
+
+#[tokio::test]
+async fn runs_middleware_around_commands() {
+    let ctx = testing::context();
+    let events = Arc::new(Mutex::new(Vec::new()));
+
+    let dispatcher = Dispatcher::new()
+        .middleware(Recorder {
+            name: "outer",
+            events: Arc::clone(&events),
+        })
+        .middleware(NoGreetings)
+        .middleware(Recorder {
+            name: "inner",
+            events: Arc::clone(&events),
+        });
+
+    let interaction = InteractionBuilder::command("ping").option("times", 2).build();
+
+    assert_eq!(dispatcher.dispatch::<AllCommands>(&ctx, &interaction).await.unwrap(), "pong pong");
+    assert_eq!(*events.lock().unwrap(), [
+        "outer before ping: Ping the bot.",
+        "inner before ping: Ping the bot.",
+        "inner after ping: pong pong",
+        "outer after ping: pong pong",
+    ]);
+
+    events.lock().unwrap().clear();
+
+    let interaction = InteractionBuilder::command("greet")
+        .option("user", testing::user(2, "bob"))
+        .option("message", "Hello")
+        .build();
+
+    assert_eq!(
+        dispatcher.dispatch::<AllCommands>(&ctx, &interaction).await.unwrap(),
+        "no greetings"
+    );
+    assert_eq!(*events.lock().unwrap(), [
+        "outer before greet: Greet a user.",
+        "outer after greet: no greetings",
+    ]);
+}
@@ -0,0 +1,225 @@
+#![cfg(any(serenity = "0_10", serenity = "0_11", serenity = "0_12"))]
+
+mod common;
+
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use common::{Config, Reset};
+use ed25519_dalek::{Signer, SigningKey};
+use rand_core::OsRng;
+use serde_json::{json, Value};
+use serenity_commands::endpoint::{self, PublicKey};
+use serenity_commands::macros::{Command, Commands, Modal};
+use serenity_commands::serenity::builder::CreateInteractionResponse;
+#[cfg(serenity = "0_12")]
+use serenity_commands::serenity::builder::CreateInteractionResponseMessage;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity_commands::serenity::model::interactions::InteractionResponseType;
+use tower::ServiceExt;
+
+const PING: &str = include_str!("fixtures/ping.json");
+const PING_COMMAND: &str = include_str!("fixtures/ping_command.json");
+const UNKNOWN_COMMAND: &str = include_str!("fixtures/unknown_command.json");
+const MISSING_SUBCOMMAND: &str = include_str!("fixtures/missing_subcommand.json");
+const MODAL_SUBMIT: &str = include_str!("fixtures/modal_submit.json");
+
+const TIMESTAMP: &str = "1638316800";
+
+/// Play a little game called Ping Pong!
+#[derive(Command)]
+#[command(name = "ping")]
+struct Ping {
+    /// Amount of pings to send.
+    #[option(integer)]
+    n: i64,
+}
+
+#[derive(Commands)]
+enum Command {
+    Ping(Ping),
+    Config(Config),
+}
+
+/// Send feedback
+#[derive(Modal)]
+#[modal(id = "feedback")]
+struct Feedback {
+    /// Subject
+    #[input(short)]
+    subject: String,
+}
+
+impl Command {
+    fn reply(&self) -> String {
+        match self {
+            Self::Ping(Ping {
+                n,
+            }) => format!("Pong {} times!", n),
+            Self::Config(Config::Reset(Reset)) => "Reset the configuration.".to_string(),
+            Self::Config(Config::Prefix(_)) => "Updated the prefix.".to_string(),
+        }
+    }
+}
+
+// The response owns its attachments, if any, so that it can be returned by
+// the handler.
+#[cfg(serenity = "0_11")]
+type Response = CreateInteractionResponse<'static>;
+#[cfg(not(serenity = "0_11"))]
+type Response = CreateInteractionResponse;
+
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+fn message(content: String) -> Response {
+    let mut response = CreateInteractionResponse::default();
+    response
+        .kind(InteractionResponseType::ChannelMessageWithSource)
+        .interaction_response_data(|m| m.content(content));
+    response
+}
+
+#[cfg(serenity = "0_12")]
+fn message(content: String) -> Response {
+    CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(content))
+}
+
+fn public_key(key: &SigningKey) -> PublicKey {
+    PublicKey::from_hex(&hex::encode(key.verifying_key().as_bytes())).unwrap()
+}
+
+fn app(key: &SigningKey) -> Router {
+    endpoint::router_with_modals(
+        public_key(key),
+        |command: Command, _| async move { message(command.reply()) },
+        |submit, _| async move {
+            match Feedback::parse_submit(submit) {
+                Ok(feedback) => {
+                    message(format!("Thanks for your feedback on {}!", feedback.subject))
+                },
+                Err(e) => message(e.to_string()),
+            }
+        },
+    )
+}
+
+fn app_without_modals(key: &SigningKey) -> Router {
+    endpoint::router(public_key(key), |command: Command, _| async move { message(command.reply()) })
+}
+
+fn request(signature: &str, body: &str) -> Request<Body> {
+    Request::post("/")
+        .header("X-Signature-Ed25519", signature)
+        .header("X-Signature-Timestamp", TIMESTAMP)
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn signed_request(key: &SigningKey, body: &str) -> Request<Body> {
+    let signature = key.sign(format!("{}{}", TIMESTAMP, body).as_bytes());
+
+    request(&hex::encode(signature.to_bytes()), body)
+}
+
+async fn send(app: Router, request: Request<Body>) -> (StatusCode, Vec<u8>) {
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    (status, body.to_vec())
+}
+
+#[tokio::test]
+async fn answers_pings() {
+    let key = SigningKey::generate(&mut OsRng);
+
+    let (status, body) = send(app(&key), signed_request(&key, PING)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(serde_json::from_slice::<Value>(&body).unwrap(), json!({ "type": 1 }));
+}
+
+#[tokio::test]
+async fn dispatches_commands() {
+    let key = SigningKey::generate(&mut OsRng);
+
+    let (status, body) = send(app(&key), signed_request(&key, PING_COMMAND)).await;
+
+    assert_eq!(status, StatusCode::OK);
+
+    // Serenity 0.12 also sends an empty list of attachments.
+    let body = serde_json::from_slice::<Value>(&body).unwrap();
+    assert_eq!(body["type"], 4);
+    assert_eq!(body["data"]["content"], "Pong 3 times!");
+}
+
+#[tokio::test]
+async fn rejects_unknown_commands() {
+    let key = SigningKey::generate(&mut OsRng);
+
+    let (status, _) = send(app(&key), signed_request(&key, UNKNOWN_COMMAND)).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn rejects_commands_without_subcommands() {
+    let key = SigningKey::generate(&mut OsRng);
+
+    let (status, body) = send(app(&key), signed_request(&key, MISSING_SUBCOMMAND)).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(String::from_utf8(body).unwrap(), "missing command or subcommand name");
+}
+
+#[tokio::test]
+async fn dispatches_modal_submissions() {
+    let key = SigningKey::generate(&mut OsRng);
+
+    let (status, body) = send(app(&key), signed_request(&key, MODAL_SUBMIT)).await;
+
+    assert_eq!(status, StatusCode::OK);
+
+    let body = serde_json::from_slice::<Value>(&body).unwrap();
+    assert_eq!(body["type"], 4);
+    assert_eq!(body["data"]["content"], "Thanks for your feedback on Bug!");
+}
+
+#[tokio::test]
+async fn rejects_modal_submissions_without_a_handler() {
+    let key = SigningKey::generate(&mut OsRng);
+
+    let (status, _) = send(app_without_modals(&key), signed_request(&key, MODAL_SUBMIT)).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn rejects_signatures_from_other_keys() {
+    let key = SigningKey::generate(&mut OsRng);
+    let other = SigningKey::generate(&mut OsRng);
+
+    let (status, _) = send(app(&key), signed_request(&other, PING)).await;
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn rejects_tampered_bodies() {
+    let key = SigningKey::generate(&mut OsRng);
+
+    let mut request = signed_request(&key, PING);
+    *request.body_mut() = Body::from(PING_COMMAND);
+
+    let (status, _) = send(app(&key), request).await;
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn rejects_malformed_signatures() {
+    let key = SigningKey::generate(&mut OsRng);
+
+    let (status, _) = send(app(&key), request("not hex", PING)).await;
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,136 @@
+#![cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serde_json::{json, Value};
+use serenity_commands::error::ParseError;
+use serenity_commands::macros::{Command, Options};
+use serenity_commands::serenity::builder::CreateApplicationCommand;
+#[cfg(serenity = "0_11")]
+use serenity_commands::serenity::json::hashmap_to_json_map;
+use serenity_commands::serenity::model::user::User;
+#[cfg(serenity = "0_10")]
+use serenity_commands::serenity::utils::hashmap_to_json_map;
+use serenity_commands::testing::{self, InteractionBuilder};
+use serenity_commands::Options as _;
+
+/// Options shared by moderation commands.
+#[derive(Debug, PartialEq, Options)]
+struct ModerationArgs {
+    /// The user to moderate.
+    #[option(user)]
+    target_user: User,
+    /// The reason for the action.
+    #[option(string)]
+    reason: Option<String>,
+    #[option(flatten)]
+    flags: Flags,
+}
+
+#[derive(Debug, PartialEq, Options)]
+#[options(rename_all = "kebab-case")]
+struct Flags {
+    /// Whether to hide the action.
+    #[option(boolean)]
+    silent_mode: Option<bool>,
+}
+
+/// Ban a user.
+#[derive(Debug, PartialEq, Command)]
+#[command(prefix)]
+struct Ban {
+    #[option(flatten)]
+    common: ModerationArgs,
+    /// Days of messages to delete.
+    #[option(integer)]
+    days: Option<i64>,
+}
+
+/// Kick a user.
+#[derive(Debug, PartialEq, Command)]
+struct Kick {
+    #[option(flatten)]
+    common: ModerationArgs,
+}
+
+fn ban() -> Ban {
+    Ban {
+        common: ModerationArgs {
+            target_user: testing::user(1, "alice"),
+            reason: Some("spam".to_string()),
+            flags: Flags {
+                silent_mode: Some(true),
+            },
+        },
+        days: Some(7),
+    }
+}
+
+#[test]
+fn counts_flattened_options() {
+    assert_eq!(Flags::COUNT, 1);
+    assert_eq!(ModerationArgs::COUNT, 3);
+}
+
+#[test]
+fn registers_flattened_options_in_order() {
+    let mut cmd = CreateApplicationCommand::default();
+    Ban::register_command(&mut cmd);
+
+    let cmd = Value::Object(hashmap_to_json_map(cmd.0));
+    let names = cmd["options"].as_array().unwrap().iter().map(|o| &o["name"]).collect::<Vec<_>>();
+
+    assert_eq!(names, [
+        &json!("target_user"),
+        &json!("reason"),
+        &json!("silent-mode"),
+        &json!("days")
+    ]);
+    assert_eq!(cmd["options"][0]["required"], true);
+
+    let mut cmd = CreateApplicationCommand::default();
+    Kick::register_command(&mut cmd);
+
+    assert_eq!(Value::Object(hashmap_to_json_map(cmd.0))["options"].as_array().unwrap().len(), 3);
+}
+
+#[test]
+fn parses_flattened_options() {
+    let interaction = InteractionBuilder::command("ban")
+        .option("days", 7)
+        .option("silent-mode", true)
+        .option("target_user", testing::user(1, "alice"))
+        .option("reason", "spam")
+        .build();
+
+    assert_eq!(Ban::parse_command(interaction.data).unwrap(), ban());
+}
+
+#[test]
+fn rejects_missing_and_unknown_flattened_options() {
+    let interaction = InteractionBuilder::command("kick").option("reason", "spam").build();
+
+    assert!(matches!(
+        Kick::parse_command(interaction.data),
+        Err(ParseError::MissingOption("target_user"))
+    ));
+
+    let interaction = InteractionBuilder::command("kick")
+        .option("target_user", testing::user(1, "alice"))
+        .option("days", 7)
+        .build();
+
+    assert!(matches!(
+        Kick::parse_command(interaction.data),
+        Err(ParseError::UnknownOption(name)) if name == "days"
+    ));
+}
+
+#[test]
+fn round_trips_flattened_options() {
+    let ban = ban();
+
+    assert_eq!(Ban::parse(ban.to_options()).unwrap(), ban);
+}
+
+#[test]
+fn parses_flattened_arguments() {
+    assert_eq!(Ban::parse_message("ban <@1> spam yes 7").unwrap().days, Some(7));
+}
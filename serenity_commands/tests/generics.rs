@@ -0,0 +1,178 @@
+#![cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serde_json::{json, Value};
+use serenity_commands::macros::{Command, Commands, Group, Options};
+use serenity_commands::serenity::builder::CreateApplicationCommand;
+#[cfg(serenity = "0_11")]
+use serenity_commands::serenity::json::hashmap_to_json_map;
+use serenity_commands::serenity::model::user::User;
+#[cfg(serenity = "0_10")]
+use serenity_commands::serenity::utils::hashmap_to_json_map;
+use serenity_commands::testing::{self, GroupBuilder, InteractionBuilder, SubCommandBuilder};
+use serenity_commands::Options as _;
+
+mod admin {
+    use serenity_commands::macros::Command;
+    use serenity_commands::serenity::model::user::User;
+
+    /// Ban a user.
+    #[derive(Debug, PartialEq, Command)]
+    #[command(prefix)]
+    pub struct Ban {
+        /// The user to ban.
+        #[option(user)]
+        pub user: User,
+    }
+}
+
+#[derive(Debug, PartialEq, Options)]
+struct Days {
+    /// Days of messages to delete.
+    #[option(integer)]
+    days: i64,
+}
+
+#[derive(Debug, PartialEq, Options)]
+struct WithReason<A> {
+    /// The reason for the action.
+    #[option(string)]
+    reason: Option<String>,
+    #[option(flatten)]
+    flags: A,
+}
+
+/// Kick a user.
+#[derive(Debug, PartialEq, Command)]
+#[command(prefix)]
+struct Kick<A> {
+    /// The user to kick.
+    #[option(user)]
+    user: User,
+    #[option(flatten)]
+    args: A,
+}
+
+/// Moderate users.
+#[derive(Debug, PartialEq, Group)]
+#[group(prefix)]
+enum Moderate<A> {
+    Kick(Kick<A>),
+    Ban(admin::Ban),
+}
+
+/// Administrate the server.
+#[derive(Debug, PartialEq, Command)]
+#[command(prefix)]
+enum Admin<A> {
+    #[option(group)]
+    Moderate(Moderate<A>),
+    #[option(subcommand)]
+    Ban(admin::Ban),
+}
+
+#[derive(Debug, PartialEq, Commands)]
+#[commands(prefix)]
+enum Commands<A> {
+    Admin(Admin<A>),
+    Kick(Kick<A>),
+    Ban(admin::Ban),
+}
+
+type Args = WithReason<Days>;
+
+fn kick() -> Kick<Args> {
+    Kick {
+        user: testing::user(1, "alice"),
+        args: WithReason {
+            reason: Some("spam".to_string()),
+            flags: Days {
+                days: 7,
+            },
+        },
+    }
+}
+
+#[test]
+fn counts_generic_options() {
+    assert_eq!(Args::COUNT, 2);
+}
+
+#[test]
+fn registers_generic_commands() {
+    let mut cmd = CreateApplicationCommand::default();
+    Kick::<Args>::register_command(&mut cmd);
+
+    let cmd = Value::Object(hashmap_to_json_map(cmd.0));
+    let names = cmd["options"].as_array().unwrap().iter().map(|o| &o["name"]).collect::<Vec<_>>();
+
+    assert_eq!(cmd["name"], "kick");
+    assert_eq!(names, [&json!("user"), &json!("reason"), &json!("days")]);
+
+    let mut cmd = CreateApplicationCommand::default();
+    Admin::<Args>::register_command(&mut cmd);
+
+    let cmd = Value::Object(hashmap_to_json_map(cmd.0));
+
+    assert_eq!(cmd["options"][0]["name"], "moderate");
+    assert_eq!(cmd["options"][0]["options"][1]["name"], "ban");
+    assert_eq!(cmd["options"][1]["name"], "ban");
+}
+
+#[test]
+fn parses_generic_commands() {
+    let interaction = InteractionBuilder::command("admin")
+        .group(
+            GroupBuilder::new("moderate").subcommand(
+                SubCommandBuilder::new("kick")
+                    .option("user", testing::user(1, "alice"))
+                    .option("reason", "spam")
+                    .option("days", 7),
+            ),
+        )
+        .build();
+
+    assert_eq!(
+        Commands::<Args>::parse(interaction).unwrap(),
+        Commands::Admin(Admin::Moderate(Moderate::Kick(kick())))
+    );
+
+    let interaction =
+        InteractionBuilder::command("ban").option("user", testing::user(2, "bob")).build();
+
+    assert_eq!(
+        Commands::<Args>::parse(interaction).unwrap(),
+        Commands::Ban(admin::Ban {
+            user: testing::user(2, "bob"),
+        })
+    );
+}
+
+#[test]
+fn round_trips_generic_commands() {
+    let kick = kick();
+
+    assert_eq!(Kick::<Args>::parse(kick.to_options()).unwrap(), kick);
+
+    let admin = Admin::<Args>::Moderate(Moderate::Ban(admin::Ban {
+        user: testing::user(2, "bob"),
+    }));
+
+    assert_eq!(Admin::<Args>::parse(admin.to_options()).unwrap(), admin);
+}
+
+#[test]
+fn parses_generic_messages() {
+    let cmd = Commands::<Args>::parse_message("admin moderate kick <@1> spam 7").unwrap();
+
+    match cmd {
+        Commands::Admin(Admin::Moderate(Moderate::Kick(kick))) => {
+            assert_eq!(kick.user.id, 1);
+            assert_eq!(kick.args.flags.days, 7);
+        },
+        _ => panic!("expected a kick"),
+    }
+
+    assert!(matches!(
+        Commands::<Args>::parse_message("admin ban <@2>").unwrap(),
+        Commands::Admin(Admin::Ban(_))
+    ));
+}
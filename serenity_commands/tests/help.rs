@@ -0,0 +1,211 @@
+#![cfg(any(serenity = "0_10", serenity = "0_11"))]
+#![allow(dead_code)]
+
+mod common;
+
+use common::{Ban, Config};
+use serde_json::Value;
+use serenity_commands::help::Help;
+use serenity_commands::info::CommandMeta;
+use serenity_commands::macros::Commands;
+use serenity_commands::model::OptionKind;
+use serenity_commands::serenity::builder::CreateEmbed;
+#[cfg(serenity = "0_11")]
+use serenity_commands::serenity::json::hashmap_to_json_map;
+#[cfg(serenity = "0_10")]
+use serenity_commands::serenity::utils::hashmap_to_json_map;
+use serenity_commands::testing::InteractionBuilder;
+use serenity_commands::Commands;
+
+#[derive(Debug, Commands)]
+enum AllCommands {
+    Ban(Ban),
+    Config(Config),
+    Help(Help),
+}
+
+fn render(help: Help) -> Value {
+    let mut embed = CreateEmbed::default();
+    help.create_embed::<AllCommands>(&mut embed);
+
+    Value::Object(hashmap_to_json_map(embed.0))
+}
+
+#[test]
+fn generates_command_metadata() {
+    let commands = AllCommands::METADATA;
+    let names = commands.iter().map(|c| c.name).collect::<Vec<_>>();
+
+    assert_eq!(names, ["ban", "config", "help"]);
+
+    let ban = commands[0];
+    assert_eq!(ban.description, "Ban a user.");
+    assert!(ban.subcommands.is_empty());
+
+    let options = ban.options();
+    assert_eq!(options.len(), 3);
+    assert_eq!(options[0].kind, OptionKind::User);
+    assert!(options[0].required);
+    assert_eq!(options[1].name, "reason");
+    assert!(!options[1].required);
+
+    let set = CommandMeta::find(commands, "config  prefix set").unwrap();
+    assert_eq!(set.description, "Set a configuration value.");
+    assert_eq!(set.options()[0].name, "key");
+
+    let reset = CommandMeta::find(commands, "config reset").unwrap();
+    assert_eq!(reset.description, "Reset the configuration.");
+
+    assert!(CommandMeta::find(commands, "config delete").is_none());
+}
+
+#[test]
+fn renders_command_list() {
+    let embed = render(Help {
+        command: None,
+        page: None,
+    });
+
+    assert_eq!(embed["title"], "Commands");
+    assert_eq!(
+        embed["description"],
+        "`/ban` — Ban a user.\n`/config` — Configure the bot.\n`/help` — Show the available commands."
+    );
+    assert_eq!(embed["footer"]["text"], "Page 1/1");
+
+    let embed = render(Help {
+        command: None,
+        page: Some(3),
+    });
+
+    assert_eq!(embed["footer"]["text"], "Page 1/1");
+}
+
+#[test]
+fn renders_command_details() {
+    let embed = render(Help {
+        command: Some("ban".to_string()),
+        page: None,
+    });
+
+    assert_eq!(embed["title"], "/ban");
+    assert_eq!(embed["fields"][0]["name"], "user");
+    assert_eq!(embed["fields"][0]["value"], "The user to ban. (user, required)");
+    assert_eq!(embed["fields"][1]["value"], "The reason for the ban. (text, optional)");
+
+    let embed = render(Help {
+        command: Some("config".to_string()),
+        page: None,
+    });
+
+    assert_eq!(embed["fields"][0]["name"], "Subcommands");
+    assert_eq!(
+        embed["fields"][0]["value"],
+        "`/config reset` — Reset the configuration.\n`/config prefix` — Manage the prefix."
+    );
+
+    let embed = render(Help {
+        command: Some("kick".to_string()),
+        page: None,
+    });
+
+    assert_eq!(embed["title"], "Unknown command");
+}
+
+#[test]
+fn paginates_commands() {
+    const PING: CommandMeta = CommandMeta {
+        name: "ping",
+        description: "Ping the bot.",
+        options: &[],
+        subcommands: &[],
+        default_member_permissions: None,
+        dm_permission: None,
+    };
+
+    let commands = [&PING; 25];
+
+    let mut embed = CreateEmbed::default();
+    Help {
+        command: None,
+        page: Some(3),
+    }
+    .render(&commands, &mut embed);
+
+    let embed = Value::Object(hashmap_to_json_map(embed.0));
+
+    assert_eq!(embed["description"].as_str().unwrap().lines().count(), 5);
+    assert_eq!(embed["footer"]["text"], "Page 3/3");
+}
+
+#[test]
+fn renders_empty_command_lists() {
+    let mut embed = CreateEmbed::default();
+    Help {
+        command: None,
+        page: None,
+    }
+    .render(&[], &mut embed);
+
+    let embed = Value::Object(hashmap_to_json_map(embed.0));
+
+    assert_eq!(embed["description"], "There are no commands.");
+    assert_eq!(embed["footer"]["text"], "Page 1/1");
+}
+
+#[test]
+fn splits_long_subcommand_lists() {
+    const SET: CommandMeta = CommandMeta {
+        name: "set",
+        description:
+            "Set a configuration value, which is a very long sentence to fill the field up.",
+        options: &[],
+        subcommands: &[],
+        default_member_permissions: None,
+        dm_permission: None,
+    };
+
+    const CONFIG: CommandMeta = CommandMeta {
+        name: "config",
+        description: "Configure the bot.",
+        options: &[],
+        subcommands: &[&SET; 25],
+        default_member_permissions: None,
+        dm_permission: None,
+    };
+
+    let commands = [&CONFIG];
+
+    let mut embed = CreateEmbed::default();
+    Help {
+        command: Some("config".to_string()),
+        page: None,
+    }
+    .render(&commands, &mut embed);
+
+    let embed = Value::Object(hashmap_to_json_map(embed.0));
+    let fields = embed["fields"].as_array().unwrap();
+
+    assert!(fields.len() > 1);
+    assert_eq!(fields[0]["name"], "Subcommands");
+    assert_eq!(fields[1]["name"], "Subcommands (continued)");
+
+    let lines = fields
+        .iter()
+        .map(|f| f["value"].as_str().unwrap())
+        .inspect(|value| assert!(value.chars().count() <= 1024))
+        .flat_map(str::lines)
+        .count();
+
+    assert_eq!(lines, 25);
+}
+
+#[test]
+fn parses_help_command() {
+    let interaction = InteractionBuilder::command("help").option("command", "config reset").build();
+
+    match AllCommands::parse(interaction).unwrap() {
+        AllCommands::Help(help) => assert_eq!(help.command.as_deref(), Some("config reset")),
+        other => panic!("expected help, got {:?}", other),
+    }
+}
@@ -0,0 +1,63 @@
+#![cfg(any(serenity = "0_10", serenity = "0_11"))]
+
+mod common;
+
+use common::{Prefix, Reset, Set};
+use serde_json::Value;
+use serenity_commands::macros::Command;
+use serenity_commands::serenity::builder::CreateApplicationCommand;
+#[cfg(serenity = "0_11")]
+use serenity_commands::serenity::json::hashmap_to_json_map;
+#[cfg(serenity = "0_10")]
+use serenity_commands::serenity::utils::hashmap_to_json_map;
+use serenity_commands::testing::{GroupBuilder, InteractionBuilder, SubCommandBuilder};
+
+/// Configure the bot, inferring which variants are groups.
+#[derive(Debug, PartialEq, Command)]
+#[command(prefix)]
+enum Config {
+    Reset(Reset),
+    Prefix(Prefix),
+    #[option(subcommand)]
+    Set(Set),
+}
+
+#[test]
+fn infers_subcommands_and_groups() {
+    let mut cmd = CreateApplicationCommand::default();
+    Config::register_command(&mut cmd);
+
+    let cmd = Value::Object(hashmap_to_json_map(cmd.0));
+    let kinds = cmd["options"].as_array().unwrap().iter().map(|o| &o["type"]).collect::<Vec<_>>();
+
+    assert_eq!(kinds, [1, 2, 1]);
+}
+
+#[test]
+fn parses_inferred_subcommands_and_groups() {
+    let interaction =
+        InteractionBuilder::command("config").subcommand(SubCommandBuilder::new("reset")).build();
+
+    assert_eq!(Config::parse_command(interaction.data).unwrap(), Config::Reset(Reset));
+
+    let interaction = InteractionBuilder::command("config")
+        .group(
+            GroupBuilder::new("prefix")
+                .subcommand(SubCommandBuilder::new("set").option("key", "?")),
+        )
+        .build();
+
+    let config = Config::parse_command(interaction.data).unwrap();
+
+    assert_eq!(
+        config,
+        Config::Prefix(Prefix::Set(Set {
+            key: "?".to_string()
+        }))
+    );
+    assert_eq!(Config::parse(config.to_options()).unwrap(), config);
+    assert_eq!(
+        Config::parse_message("config prefix reset").unwrap(),
+        Config::Prefix(Prefix::Reset(Reset))
+    );
+}
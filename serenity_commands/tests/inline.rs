@@ -0,0 +1,151 @@
+#![cfg(any(serenity = "0_10", serenity = "0_11"))]
+
+mod common;
+
+use common::Set;
+use serde_json::{json, Value};
+use serenity_commands::error::ParseError;
+use serenity_commands::macros::{Command, Options};
+use serenity_commands::serenity::builder::CreateApplicationCommand;
+#[cfg(serenity = "0_11")]
+use serenity_commands::serenity::json::hashmap_to_json_map;
+use serenity_commands::serenity::model::user::User;
+#[cfg(serenity = "0_10")]
+use serenity_commands::serenity::utils::hashmap_to_json_map;
+use serenity_commands::testing::{self, InteractionBuilder, SubCommandBuilder};
+
+#[derive(Debug, PartialEq, Options)]
+struct Notify {
+    /// Whether to notify the user.
+    #[option(boolean)]
+    notify: Option<bool>,
+}
+
+/// Configure the bot.
+#[derive(Debug, PartialEq, Command)]
+#[command(prefix, rename_all = "kebab-case")]
+enum Config {
+    /// Documentation of tuple variants is left to their type, and can span
+    /// several lines.
+    #[option(subcommand)]
+    Set(Set),
+    /// Reset the configuration,
+    /// all of it.
+    Reset,
+    /// Block a user.
+    BlockUser {
+        /// The user to block.
+        #[option(user)]
+        user: User,
+        /// How many days to block the user for.
+        #[option(integer)]
+        days: Option<i64>,
+        #[option(flatten)]
+        flags: Notify,
+    },
+    /// Show the configuration.
+    #[option(subcommand, name = "display")]
+    Show {},
+}
+
+fn register() -> Value {
+    let mut cmd = CreateApplicationCommand::default();
+    Config::register_command(&mut cmd);
+
+    Value::Object(hashmap_to_json_map(cmd.0))
+}
+
+#[test]
+fn registers_inline_subcommands() {
+    let cmd = register();
+    let subcommands = cmd["options"].as_array().unwrap();
+
+    let names = subcommands.iter().map(|o| &o["name"]).collect::<Vec<_>>();
+    assert_eq!(names, [&json!("set"), &json!("reset"), &json!("block-user"), &json!("display")]);
+
+    assert_eq!(subcommands[0]["description"], "Set a configuration value.");
+    assert_eq!(subcommands[1]["description"], "Reset the configuration, all of it.");
+    assert_eq!(subcommands[1]["type"], 1);
+
+    let options = subcommands[2]["options"].as_array().unwrap();
+    let names = options.iter().map(|o| &o["name"]).collect::<Vec<_>>();
+    assert_eq!(names, [&json!("user"), &json!("days"), &json!("notify")]);
+    assert_eq!(options[0]["required"], true);
+}
+
+#[test]
+fn parses_inline_subcommands() {
+    let interaction =
+        InteractionBuilder::command("config").subcommand(SubCommandBuilder::new("reset")).build();
+
+    assert_eq!(Config::parse_command(interaction.data).unwrap(), Config::Reset);
+
+    let interaction = InteractionBuilder::command("config")
+        .subcommand(
+            SubCommandBuilder::new("block-user")
+                .option("user", testing::user(1, "alice"))
+                .option("notify", true),
+        )
+        .build();
+
+    assert_eq!(Config::parse_command(interaction.data).unwrap(), Config::BlockUser {
+        user: testing::user(1, "alice"),
+        days: None,
+        flags: Notify {
+            notify: Some(true)
+        },
+    });
+}
+
+#[test]
+fn rejects_invalid_inline_subcommands() {
+    let interaction = InteractionBuilder::command("config")
+        .subcommand(SubCommandBuilder::new("block-user").option("days", 3))
+        .build();
+
+    assert!(matches!(
+        Config::parse_command(interaction.data),
+        Err(ParseError::MissingOption("user"))
+    ));
+
+    let interaction = InteractionBuilder::command("config")
+        .subcommand(SubCommandBuilder::new("reset").option("days", 3))
+        .build();
+
+    assert!(matches!(
+        Config::parse_command(interaction.data),
+        Err(ParseError::UnknownOption(name)) if name == "days"
+    ));
+}
+
+#[test]
+fn round_trips_inline_subcommands() {
+    for config in [Config::Reset, Config::Show {}, Config::BlockUser {
+        user: testing::user(1, "alice"),
+        days: Some(7),
+        flags: Notify {
+            notify: None,
+        },
+    }] {
+        assert_eq!(Config::parse(config.to_options()).unwrap(), config);
+    }
+}
+
+#[test]
+fn parses_inline_subcommand_arguments() {
+    assert_eq!(Config::parse_message("config reset").unwrap(), Config::Reset);
+    assert_eq!(Config::parse_message("config display").unwrap(), Config::Show {});
+
+    match Config::parse_message("config block-user <@1> 7 yes").unwrap() {
+        Config::BlockUser {
+            user,
+            days,
+            flags,
+        } => {
+            assert_eq!(user.id, 1);
+            assert_eq!(days, Some(7));
+            assert_eq!(flags.notify, Some(true));
+        },
+        other => panic!("expected a block, got {:?}", other),
+    }
+}
@@ -0,0 +1,171 @@
+mod common;
+
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use common::Ban;
+use common::{Config, Prefix, Reset, Set};
+use serenity_commands::error::ParseError;
+use serenity_commands::macros::{Command, Commands};
+use serenity_commands::message::tokenize;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity_commands::serenity::model::channel::PartialChannel;
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serenity_commands::serenity::model::guild::Role;
+#[cfg(serenity = "0_12")]
+use serenity_commands::serenity::model::id::GenericId;
+#[cfg(twilight)]
+use serenity_commands::twilight_model::id::{marker::GenericMarker, Id};
+
+#[cfg(twilight)]
+type GenericId = Id<GenericMarker>;
+
+/// Ping the bot.
+#[derive(Debug, PartialEq, Command)]
+#[command(name = "ping", prefix)]
+struct Ping {
+    /// How many times to ping.
+    #[option(integer)]
+    times: Option<i64>,
+}
+
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+/// Announce something.
+#[derive(Debug, Command)]
+#[command(name = "announce", prefix)]
+struct Announce {
+    /// The channel to announce in.
+    #[option(channel)]
+    channel: PartialChannel,
+    /// The role to ping.
+    #[option(role)]
+    role: Role,
+    /// Hours until the announcement.
+    #[option(number)]
+    hours: f64,
+}
+
+/// Poke a user or role.
+#[cfg(any(serenity = "0_12", twilight))]
+#[derive(Debug, Command)]
+#[command(name = "poke", prefix)]
+struct Poke {
+    /// The user or role to poke.
+    #[option(mention)]
+    target: GenericId,
+}
+
+#[derive(Debug, PartialEq, Commands)]
+#[commands(prefix)]
+enum AllCommands {
+    Ping(Ping),
+    Config(Config),
+}
+
+#[test]
+fn tokenizes_quoted_arguments() {
+    assert_eq!(tokenize(r#"  ban <@1>  "spamming \"links\""  yes "#).unwrap(), [
+        "ban",
+        "<@1>",
+        r#"spamming "links""#,
+        "yes"
+    ]);
+    assert!(matches!(tokenize(r#"ban <@1> "spam"#), Err(ParseError::UnclosedQuote)));
+}
+
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+#[test]
+fn parses_options_by_position() {
+    assert_eq!(Ping::parse_message("ping 3").unwrap(), Ping {
+        times: Some(3)
+    });
+    assert_eq!(Ping::parse_message("ping").unwrap(), Ping {
+        times: None
+    });
+
+    let ban = Ban::parse_message(r#"ban <@!42> "being rude" 7"#).unwrap();
+    assert_eq!(ban.user.id, 42);
+    assert_eq!(ban.reason.as_deref(), Some("being rude"));
+    assert_eq!(ban.days, Some(7));
+}
+
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+#[test]
+fn parses_mentions() {
+    let announce = Announce::parse_message("announce <#7> <@&8> 1.5").unwrap();
+    assert_eq!(announce.channel.id, 7);
+    assert_eq!(announce.role.id, 8);
+    assert_eq!(announce.hours, 1.5);
+
+    let announce = Announce::parse_message("announce 7 8 2").unwrap();
+    assert_eq!(announce.channel.id, 7);
+    assert_eq!(announce.role.id, 8);
+
+    assert!(matches!(
+        Announce::parse_message("announce <@7> <@&8> 1"),
+        Err(ParseError::InvalidArgument("channel"))
+    ));
+}
+
+#[test]
+fn parses_subcommands_and_groups() {
+    assert_eq!(Config::parse_message("config reset").unwrap(), Config::Reset(Reset));
+    assert_eq!(
+        Config::parse_message("config prefix set ?").unwrap(),
+        Config::Prefix(Prefix::Set(Set {
+            key: "?".to_string()
+        }))
+    );
+    assert!(matches!(Config::parse_message("config prefix"), Err(ParseError::MissingName)));
+    assert!(matches!(
+        Config::parse_message("config delete"),
+        Err(ParseError::UnknownSubCommand(name)) if name == "delete"
+    ));
+}
+
+#[test]
+fn parses_commands() {
+    assert_eq!(
+        AllCommands::parse_message("ping 2").unwrap(),
+        AllCommands::Ping(Ping {
+            times: Some(2)
+        })
+    );
+    assert_eq!(
+        AllCommands::parse_message("config prefix reset").unwrap(),
+        AllCommands::Config(Config::Prefix(Prefix::Reset(Reset)))
+    );
+    assert!(matches!(
+        AllCommands::parse_message("kick"),
+        Err(ParseError::UnknownCommand(name)) if name == "kick"
+    ));
+    assert!(matches!(AllCommands::parse_message(""), Err(ParseError::MissingName)));
+}
+
+#[cfg(any(serenity = "0_10", serenity = "0_11"))]
+#[test]
+fn rejects_invalid_arguments() {
+    assert!(matches!(Ping::parse_message("ping many"), Err(ParseError::InvalidArgument("times"))));
+    assert!(matches!(
+        Ping::parse_message("ping 1 2"),
+        Err(ParseError::UnexpectedArgument(arg)) if arg == "2"
+    ));
+    assert!(matches!(Ban::parse_message("ban"), Err(ParseError::MissingOption("user"))));
+    assert!(matches!(
+        Ban::parse_message("ban <@1> rude many"),
+        Err(ParseError::InvalidArgument("delete-days"))
+    ));
+}
+
+#[cfg(any(serenity = "0_12", twilight))]
+#[test]
+fn parses_mentionables() {
+    for (mention, id) in [("<@1>", 1), ("<@!2>", 2), ("<@&3>", 3), ("4", 4)] {
+        let poke = Poke::parse_message(&format!("poke {}", mention)).unwrap();
+        assert_eq!(poke.target.get(), id);
+    }
+
+    assert!(matches!(Poke::parse_message("poke <#5>"), Err(ParseError::InvalidArgument("target"))));
+
+    let mut args = serenity_commands::message::Arguments::new("<@&6>").unwrap();
+    assert_eq!(args.mention("target").unwrap().map(|id| id.get()), Some(6));
+    assert_eq!(args.mention("target").unwrap(), None);
+}
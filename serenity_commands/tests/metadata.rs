@@ -0,0 +1,93 @@
+#![cfg(any(serenity = "0_10", serenity = "0_11"))]
+#![allow(dead_code)]
+
+mod common;
+
+use common::Config;
+use serenity_commands::info::{CommandMeta, OptionEntry};
+use serenity_commands::macros::{Command, Commands, Options};
+use serenity_commands::model::OptionKind;
+use serenity_commands::serenity::model::user::User;
+use serenity_commands::Commands;
+
+#[derive(Debug, Options)]
+struct Reason {
+    /// The reason for the action.
+    #[option(string)]
+    reason: Option<String>,
+}
+
+/// Kick a user.
+#[derive(Debug, Command)]
+struct Kick<A> {
+    /// The user to kick.
+    #[option(user)]
+    user: User,
+    #[option(flatten)]
+    args: A,
+    /// Whether to notify the user.
+    #[option(boolean)]
+    notify: Option<bool>,
+}
+
+#[derive(Debug, Commands)]
+enum AllCommands {
+    Kick(Kick<Reason>),
+    Config(Config),
+}
+
+const KICK: &CommandMeta = Kick::<Reason>::METADATA;
+
+#[test]
+fn describes_options() {
+    assert_eq!(KICK.name, "kick");
+    assert_eq!(KICK.description, "Kick a user.");
+    assert!(KICK.subcommands.is_empty());
+
+    assert!(
+        matches!(KICK.options[1], OptionEntry::Flatten([OptionEntry::Option(o)]) if o.name == "reason")
+    );
+
+    let options = KICK.options();
+    let names = options.iter().map(|o| o.name).collect::<Vec<_>>();
+
+    assert_eq!(names, ["user", "reason", "notify"]);
+    assert_eq!(options[0].kind, OptionKind::User);
+    assert!(options[0].required);
+    assert_eq!(options[1].kind, OptionKind::String);
+    assert!(!options[1].required);
+}
+
+#[test]
+fn describes_subcommands() {
+    let config = Config::METADATA;
+
+    assert!(config.options.is_empty());
+    assert_eq!(config.subcommands.len(), 2);
+
+    let reset = config.subcommands[0];
+    assert_eq!(reset.name, "reset");
+    assert_eq!(reset.description, "Reset the configuration.");
+    assert!(reset.options().is_empty());
+
+    let prefix = config.subcommands[1];
+    assert_eq!(prefix.name, "prefix");
+    assert_eq!(prefix.subcommands[0].name, "set");
+    assert_eq!(prefix.subcommands[0].options()[0].name, "key");
+    assert_eq!(prefix.subcommands[0].options()[0].kind, OptionKind::String);
+}
+
+#[test]
+fn iterates_commands() {
+    let names = AllCommands::metadata().map(|c| c.name).collect::<Vec<_>>();
+
+    assert_eq!(names, ["kick", "config"]);
+}
+
+#[test]
+fn finds_subcommands() {
+    let set = CommandMeta::find(AllCommands::METADATA, "config prefix  set").unwrap();
+    assert_eq!(set.description, "Set a configuration value.");
+
+    assert!(CommandMeta::find(AllCommands::METADATA, "config delete").is_none());
+}
@@ -0,0 +1,156 @@
+#![cfg(any(serenity = "0_10", serenity = "0_11"))]
+use std::collections::BTreeMap;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+
+use metrics::atomics::AtomicU64;
+use metrics::{
+    Counter,
+    Gauge,
+    Histogram,
+    HistogramFn,
+    Key,
+    KeyName,
+    Metadata,
+    Recorder,
+    SharedString,
+    Unit,
+};
+use serenity_commands::dispatch::{Dispatcher, Invocation, Middleware};
+use serenity_commands::macros::Commands;
+use serenity_commands::metrics::{CHECK_FAILURES, HANDLER_DURATION, INVOCATIONS, PARSE_ERRORS};
+use serenity_commands::serenity::client::Context;
+use serenity_commands::serenity::model::interactions::application_command::ApplicationCommandInteraction;
+use serenity_commands::testing::{self, InteractionBuilder};
+
+/// Ping the bot.
+#[serenity_commands::command]
+async fn ping(_ctx: &Context, _interaction: &ApplicationCommandInteraction) -> String {
+    "pong".to_string()
+}
+
+/// Kick a user.
+#[serenity_commands::command]
+async fn kick(
+    _ctx: &Context,
+    _interaction: &ApplicationCommandInteraction,
+    /// The user to kick.
+    user: String,
+) -> String {
+    format!("kicked {}", user)
+}
+
+#[derive(Debug, Commands)]
+#[commands(dispatch)]
+enum AllCommands {
+    Ping(Ping),
+    Kick(Kick),
+}
+
+/// Stops `kick` from running.
+struct NoKicking;
+
+#[serenity_commands::serenity::async_trait]
+impl Middleware<String> for NoKicking {
+    async fn before(&self, invocation: &Invocation<'_>) -> Option<String> {
+        (invocation.path == "kick").then(|| "not allowed".to_string())
+    }
+}
+
+#[derive(Default)]
+struct Values(Mutex<Vec<f64>>);
+
+impl HistogramFn for Values {
+    fn record(&self, value: f64) {
+        self.0.lock().unwrap().push(value);
+    }
+}
+
+/// Records the counters and histograms, keyed by their name and labels.
+#[derive(Default)]
+struct TestRecorder {
+    counters: Mutex<BTreeMap<String, Arc<AtomicU64>>>,
+    histograms: Mutex<BTreeMap<String, Arc<Values>>>,
+}
+
+fn key_string(key: &Key) -> String {
+    let labels =
+        key.labels().map(|l| format!("{}={}", l.key(), l.value())).collect::<Vec<_>>().join(",");
+
+    format!("{}{{{}}}", key.name(), labels)
+}
+
+impl Recorder for TestRecorder {
+    fn describe_counter(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn describe_gauge(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn describe_histogram(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _metadata: &Metadata<'_>) -> Counter {
+        let mut counters = self.counters.lock().unwrap();
+
+        Counter::from_arc(Arc::clone(counters.entry(key_string(key)).or_default()))
+    }
+
+    fn register_gauge(&self, _key: &Key, _metadata: &Metadata<'_>) -> Gauge {
+        Gauge::noop()
+    }
+
+    fn register_histogram(&self, key: &Key, _metadata: &Metadata<'_>) -> Histogram {
+        let mut histograms = self.histograms.lock().unwrap();
+
+        Histogram::from_arc(Arc::clone(histograms.entry(key_string(key)).or_default()))
+    }
+}
+
+impl TestRecorder {
+    fn counter(&self, key: &str) -> u64 {
+        let counters = self.counters.lock().unwrap();
+
+        counters.get(key).map_or(0, |c| c.load(Ordering::Acquire))
+    }
+
+    fn histogram(&self, key: &str) -> Vec<f64> {
+        let histograms = self.histograms.lock().unwrap();
+
+        histograms.get(key).map_or_else(Vec::new, |h| h.0.lock().unwrap().clone())
+    }
+}
+
+#[tokio::test]
+async fn records_dispatched_commands() {
+    let recorder = TestRecorder::default();
+    let _default = metrics::set_default_local_recorder(&recorder);
+
+    serenity_commands::metrics::describe();
+
+    let ctx = testing::context();
+    let dispatcher = Dispatcher::new().middleware(NoKicking);
+
+    for _ in 0..2 {
+        let interaction = InteractionBuilder::command("ping").build();
+        assert_eq!(dispatcher.dispatch::<AllCommands>(&ctx, &interaction).await.unwrap(), "pong");
+    }
+
+    let interaction = InteractionBuilder::command("kick").option("user", "bob").build();
+    assert_eq!(
+        dispatcher.dispatch::<AllCommands>(&ctx, &interaction).await.unwrap(),
+        "not allowed"
+    );
+
+    let interaction = InteractionBuilder::command("ban").build();
+    assert!(dispatcher.dispatch::<AllCommands>(&ctx, &interaction).await.is_err());
+
+    let interaction = InteractionBuilder::command("kick").build();
+    assert!(dispatcher.dispatch::<AllCommands>(&ctx, &interaction).await.is_err());
+
+    assert_eq!(recorder.counter(&format!("{}{{path=ping}}", INVOCATIONS)), 2);
+    assert_eq!(recorder.counter(&format!("{}{{path=kick}}", INVOCATIONS)), 1);
+    assert_eq!(recorder.counter(&format!("{}{{path=kick}}", CHECK_FAILURES)), 1);
+    assert_eq!(recorder.counter(&format!("{}{{error=unknown_command}}", PARSE_ERRORS)), 1);
+    assert_eq!(recorder.counter(&format!("{}{{error=missing_option}}", PARSE_ERRORS)), 1);
+
+    assert_eq!(recorder.histogram(&format!("{}{{path=ping}}", HANDLER_DURATION)).len(), 2);
+    assert!(recorder.histogram(&format!("{}{{path=kick}}", HANDLER_DURATION)).is_empty());
+}
@@ -0,0 +1,151 @@
+#![cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serde_json::{json, Value};
+use serenity_commands::error::ParseError;
+use serenity_commands::macros::Modal;
+use serenity_commands::modal::ModalSubmit;
+use serenity_commands::serenity::builder::CreateInteractionResponse;
+#[cfg(serenity = "0_11")]
+use serenity_commands::serenity::json::hashmap_to_json_map;
+#[cfg(serenity = "0_10")]
+use serenity_commands::serenity::utils::hashmap_to_json_map;
+
+/// Send feedback
+#[derive(Debug, PartialEq, Modal)]
+#[modal(id = "feedback")]
+struct Feedback {
+    /// Subject
+    #[input(short, max_len = 100)]
+    subject: String,
+    /// Rating out of 10
+    #[input(name = "score", min_len = 1, max_len = 2)]
+    rating: Option<u8>,
+    /// Details
+    #[input(paragraph, placeholder = "Tell us more")]
+    details: Option<String>,
+}
+
+fn submit(custom_id: &str, values: &[(&str, &str)]) -> Value {
+    let rows = values
+        .iter()
+        .map(|(id, value)| {
+            json!({
+                "type": 1,
+                "components": [{ "type": 4, "custom_id": id, "value": value }],
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "type": 5,
+        "data": { "custom_id": custom_id, "components": rows },
+    })
+}
+
+#[test]
+fn builds_modals() {
+    let mut response = CreateInteractionResponse::default();
+    Feedback::create_modal(&mut response);
+
+    assert_eq!(
+        Value::Object(hashmap_to_json_map(response.0)),
+        json!({
+            "type": 9,
+            "data": {
+                "custom_id": "feedback",
+                "title": "Send feedback",
+                "components": [
+                    {
+                        "type": 1,
+                        "components": [{
+                            "type": 4,
+                            "custom_id": "subject",
+                            "label": "Subject",
+                            "style": 1,
+                            "required": true,
+                            "max_length": 100,
+                        }],
+                    },
+                    {
+                        "type": 1,
+                        "components": [{
+                            "type": 4,
+                            "custom_id": "score",
+                            "label": "Rating out of 10",
+                            "style": 1,
+                            "required": false,
+                            "min_length": 1,
+                            "max_length": 2,
+                        }],
+                    },
+                    {
+                        "type": 1,
+                        "components": [{
+                            "type": 4,
+                            "custom_id": "details",
+                            "label": "Details",
+                            "style": 2,
+                            "required": false,
+                            "placeholder": "Tell us more",
+                        }],
+                    },
+                ],
+            },
+        })
+    );
+}
+
+#[test]
+fn parses_submissions() {
+    let payload = submit("feedback", &[("subject", "Bug"), ("score", "7"), ("details", "")]);
+
+    assert_eq!(Feedback::parse_json(&payload).unwrap(), Feedback {
+        subject: "Bug".to_string(),
+        rating: Some(7),
+        details: None,
+    });
+}
+
+#[cfg(serenity = "0_11")]
+#[test]
+fn parses_submit_interactions() {
+    use serenity_commands::serenity::model::interactions::modal::ModalSubmitInteraction;
+
+    let interaction: ModalSubmitInteraction =
+        serde_json::from_str(include_str!("fixtures/modal_submit.json")).unwrap();
+
+    assert_eq!(Feedback::parse(&interaction).unwrap(), Feedback {
+        subject: "Bug".to_string(),
+        rating: None,
+        details: None,
+    });
+}
+
+#[test]
+fn rejects_unknown_modals() {
+    let submit = ModalSubmit::from_json(&submit("report", &[("subject", "Bug")])).unwrap();
+
+    assert!(matches!(
+        Feedback::parse_submit(submit),
+        Err(ParseError::UnknownModal(id)) if id == "report"
+    ));
+}
+
+#[test]
+fn rejects_missing_and_invalid_inputs() {
+    assert!(matches!(
+        Feedback::parse_json(&submit("feedback", &[("subject", "")])),
+        Err(ParseError::MissingInput("subject"))
+    ));
+    assert!(matches!(
+        Feedback::parse_json(&submit("feedback", &[("subject", "Bug"), ("score", "ten")])),
+        Err(ParseError::InvalidInput("score"))
+    ));
+}
+
+#[test]
+fn rejects_other_interactions() {
+    let mut payload = submit("feedback", &[]);
+    payload["type"] = json!(2);
+
+    assert!(matches!(Feedback::parse_json(&payload), Err(ParseError::InvalidPayload(_))));
+}
@@ -0,0 +1,175 @@
+#![cfg(any(serenity = "0_10", serenity = "0_11"))]
+#![allow(dead_code)]
+
+mod common;
+
+use common::{Ban, Config, Prefix, Set};
+use serde_json::{json, Value};
+use serenity_commands::error::ParseError;
+use serenity_commands::macros::Commands;
+use serenity_commands::model::{
+    CommandDefinition,
+    InteractionOption,
+    InteractionOptions,
+    InteractionValue,
+    OptionKind,
+};
+use serenity_commands::serenity::builder::CreateApplicationCommand;
+#[cfg(serenity = "0_11")]
+use serenity_commands::serenity::json::hashmap_to_json_map;
+use serenity_commands::serenity::model::interactions::application_command::{
+    ApplicationCommand,
+    ApplicationCommandInteractionData,
+};
+#[cfg(serenity = "0_10")]
+use serenity_commands::serenity::utils::hashmap_to_json_map;
+use serenity_commands::testing::{self, GroupBuilder, InteractionBuilder, SubCommandBuilder};
+
+#[derive(Commands)]
+enum AllCommands {
+    Ban(Ban),
+    Config(Config),
+}
+
+fn registered(definition: CommandDefinition) -> Value {
+    Value::Object(hashmap_to_json_map(CreateApplicationCommand::from(definition).0))
+}
+
+#[test]
+fn serializes_definitions_as_registered() {
+    for definition in AllCommands::definitions() {
+        assert_eq!(serde_json::to_value(&definition).unwrap(), registered(definition));
+    }
+}
+
+#[test]
+fn defines_subcommands_and_groups() {
+    let config = Config::definition();
+
+    let kinds = config.options.iter().map(|o| (&o.name[..], o.kind)).collect::<Vec<_>>();
+    assert_eq!(kinds, [("reset", OptionKind::SubCommand), ("prefix", OptionKind::SubCommandGroup)]);
+
+    let set = &config.options[1].options[0];
+    assert_eq!(set.options[0].name, "key");
+    assert!(set.options[0].required);
+}
+
+#[test]
+fn defines_permissions() {
+    let config = Config::definition();
+
+    assert_eq!(config.default_member_permissions, Some(1 << 5));
+    assert_eq!(config.dm_permission, Some(false));
+    assert_eq!(Ban::definition().default_member_permissions, None);
+
+    let definition = serde_json::to_value(&config).unwrap();
+    assert_eq!(definition["default_member_permissions"], "32");
+    assert_eq!(definition["dm_permission"], false);
+}
+
+#[test]
+fn deserializes_definitions() {
+    let definition = serde_json::to_value(Config::definition()).unwrap();
+
+    assert_eq!(
+        serde_json::from_value::<CommandDefinition>(definition).unwrap(),
+        Config::definition()
+    );
+}
+
+#[test]
+fn reads_registered_commands() {
+    let command: ApplicationCommand = serde_json::from_value(json!({
+        "id": "1",
+        "application_id": "1",
+        "type": 1,
+        "name": "ban",
+        "description": "Ban a user.",
+        "version": "1",
+        "options": [
+            { "type": 6, "name": "user", "description": "The user to ban.", "required": true },
+            { "type": 3, "name": "reason", "description": "The reason for the ban." },
+            { "type": 4, "name": "delete-days", "description": "Days of messages to delete." },
+        ],
+    }))
+    .unwrap();
+
+    assert_eq!(CommandDefinition::try_from(&command).unwrap(), Ban::definition());
+}
+
+#[test]
+fn converts_interactions() {
+    let options = InteractionBuilder::command("ban")
+        .option("user", testing::user(2, "alice"))
+        .option("delete-days", 7)
+        .build_options();
+
+    assert_eq!(options, InteractionOptions {
+        name: "ban".to_string(),
+        options: vec![
+            InteractionOption::new("user", InteractionValue::User(2)),
+            InteractionOption::new("delete-days", InteractionValue::Integer(7)),
+        ],
+    });
+}
+
+#[test]
+fn keeps_resolved_values() {
+    let options = InteractionBuilder::command("ban")
+        .option("user", testing::user(2, "alice"))
+        .build_options();
+
+    let ban = Ban::parse(options.options).unwrap();
+
+    assert_eq!(ban.user.name, "alice");
+    assert_eq!(ban.days, None);
+}
+
+#[test]
+fn parses_converted_options() {
+    let options = InteractionBuilder::command("config")
+        .group(
+            GroupBuilder::new("prefix")
+                .subcommand(SubCommandBuilder::new("set").option("key", "!")),
+        )
+        .build_options();
+
+    let data = ApplicationCommandInteractionData::try_from(&options).unwrap();
+
+    assert_eq!(
+        Config::parse_command(data).unwrap(),
+        Config::Prefix(Prefix::Set(Set {
+            key: "!".to_string()
+        }))
+    );
+}
+
+#[test]
+fn round_trips_options_through_json() {
+    let options = serde_json::from_value::<InteractionOptions>(json!({
+        "name": "config",
+        "options": [{
+            "name": "prefix",
+            "type": 2,
+            "options": [{ "name": "reset", "type": 1 }],
+        }],
+    }))
+    .unwrap();
+
+    let json = serde_json::to_value(&options).unwrap();
+
+    assert_eq!(json["options"][0]["options"][0]["options"], json!([]));
+    assert_eq!(serde_json::from_value::<InteractionOptions>(json).unwrap(), options);
+}
+
+#[test]
+fn rejects_invalid_values() {
+    let err = serde_json::from_value::<InteractionOption>(json!({
+        "name": "key",
+        "type": 3,
+        "value": 1,
+    }))
+    .unwrap_err();
+
+    assert_eq!(err.to_string(), ParseError::InvalidType(OptionKind::String.into()).to_string());
+}
@@ -0,0 +1,125 @@
+#![cfg(any(serenity = "0_10", serenity = "0_11"))]
+use serde_json::{json, Value};
+use serenity_commands::macros::{Command, Group};
+use serenity_commands::serenity::builder::{
+    CreateApplicationCommand,
+    CreateApplicationCommandOption,
+};
+use serenity_commands::serenity::client::Context;
+#[cfg(serenity = "0_11")]
+use serenity_commands::serenity::json::hashmap_to_json_map;
+use serenity_commands::serenity::model::interactions::application_command::ApplicationCommandInteraction;
+#[cfg(serenity = "0_10")]
+use serenity_commands::serenity::utils::hashmap_to_json_map;
+use serenity_commands::testing::{GroupBuilder, InteractionBuilder, SubCommandBuilder};
+
+/// Show the server leaderboard.
+#[derive(Debug, PartialEq, Command)]
+#[command(rename_all = "kebab-case")]
+struct ServerLeaderboard {
+    /// Number of entries to show.
+    #[option(integer)]
+    max_entries: Option<i64>,
+    /// Whether to include bots.
+    #[option(boolean, name = "bots")]
+    include_bots: Option<bool>,
+}
+
+/// Reset the prefix.
+#[derive(Debug, PartialEq, Command)]
+#[command(rename_all = "lowercase")]
+struct ResetPrefix;
+
+/// Manage the prefix.
+#[derive(Debug, PartialEq, Group)]
+enum PrefixSettings {
+    ResetPrefix(ResetPrefix),
+}
+
+/// Roll some dice.
+#[serenity_commands::command(rename_all = "kebab-case")]
+async fn roll_dice(
+    _ctx: &Context,
+    _interaction: &ApplicationCommandInteraction,
+    /// Number of sides.
+    side_count: i64,
+) -> i64 {
+    side_count
+}
+
+fn register(f: fn(&mut CreateApplicationCommand) -> &mut CreateApplicationCommand) -> Value {
+    let mut cmd = CreateApplicationCommand::default();
+    f(&mut cmd);
+
+    Value::Object(hashmap_to_json_map(cmd.0))
+}
+
+#[test]
+fn infers_names_and_renames_options() {
+    assert_eq!(
+        register(ServerLeaderboard::register_command),
+        json!({
+            "name": "server-leaderboard",
+            "description": "Show the server leaderboard.",
+            "options": [
+                {
+                    "type": 4,
+                    "name": "max-entries",
+                    "description": "Number of entries to show.",
+                },
+                {
+                    "type": 5,
+                    "name": "bots",
+                    "description": "Whether to include bots.",
+                },
+            ],
+        })
+    );
+
+    assert_eq!(ResetPrefix::name(), "resetprefix");
+    assert_eq!(PrefixSettings::name(), "prefix_settings");
+    assert_eq!(RollDice::name(), "roll-dice");
+}
+
+#[test]
+fn renames_function_options() {
+    assert_eq!(register(RollDice::register_command)["options"][0]["name"], "side-count");
+}
+
+#[test]
+fn parses_renamed_options() {
+    let interaction = InteractionBuilder::command("server-leaderboard")
+        .option("max-entries", 10)
+        .option("bots", true)
+        .build();
+
+    assert_eq!(ServerLeaderboard::parse_command(interaction.data).unwrap(), ServerLeaderboard {
+        max_entries: Some(10),
+        include_bots: Some(true),
+    });
+}
+
+#[test]
+fn parses_renamed_groups() {
+    let options = InteractionBuilder::command("config")
+        .group(
+            GroupBuilder::new("prefix_settings").subcommand(SubCommandBuilder::new("resetprefix")),
+        )
+        .build_options();
+
+    let option = options.options.into_iter().next().unwrap();
+
+    assert_eq!(
+        PrefixSettings::parse_subcommand_group(option).unwrap(),
+        PrefixSettings::ResetPrefix(ResetPrefix)
+    );
+}
+
+#[test]
+fn renames_groups() {
+    let mut opt = CreateApplicationCommandOption::default();
+    PrefixSettings::register_subcommand_group(&mut opt);
+
+    assert_eq!(opt.0["name"], "prefix_settings");
+    assert_eq!(opt.0["options"][0]["name"], "resetprefix");
+}
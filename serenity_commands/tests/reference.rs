@@ -0,0 +1,70 @@
+#![cfg(any(serenity = "0_10", serenity = "0_11"))]
+#![allow(dead_code)]
+
+mod common;
+
+use common::{Ban, Config};
+use insta::assert_snapshot;
+use serenity_commands::macros::{Command, Commands};
+use serenity_commands::reference::{self, Format};
+use serenity_commands::serenity::model::user::User;
+
+/// Kick a user | a <bot>.
+#[derive(Debug, Command)]
+#[command(default_member_permissions = "KICK_MEMBERS | MODERATE_MEMBERS")]
+struct Kick {
+    /// The user to kick.
+    #[option(user)]
+    user: User,
+    /// The reason for the kick, shown in the <audit log> | modlog.
+    #[option(string)]
+    reason: Option<String>,
+}
+
+#[derive(Debug, Commands)]
+enum AllCommands {
+    Ban(Ban),
+    Kick(Kick),
+    Config(Config),
+}
+
+#[test]
+fn generates_markdown() {
+    assert_snapshot!("markdown", reference::generate::<AllCommands>(Format::Markdown));
+}
+
+#[test]
+fn generates_html() {
+    assert_snapshot!("html", reference::generate::<AllCommands>(Format::Html));
+}
+
+#[test]
+fn parses_formats() {
+    assert_eq!("md".parse::<Format>().unwrap(), Format::Markdown);
+    assert_eq!("html".parse::<Format>().unwrap(), Format::Html);
+    assert!("pdf".parse::<Format>().is_err());
+}
+
+#[test]
+fn writes_reference_to_file() {
+    let path = std::env::temp_dir().join(format!("reference-{}.html", std::process::id()));
+
+    let args = ["--format", "html", "--output", path.to_str().unwrap()];
+    reference::run::<AllCommands>(args.iter().map(|s| s.to_string())).unwrap();
+
+    let written = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(written, reference::generate::<AllCommands>(Format::Html));
+
+    let args = ["--format"].iter().map(|s| s.to_string());
+    assert!(reference::run::<AllCommands>(args).is_err());
+}
+
+#[test]
+fn rejects_unknown_flags() {
+    let args = ["--format", "html", "--verbose"].iter().map(|s| s.to_string());
+    let err = reference::run::<AllCommands>(args).unwrap_err();
+
+    assert_eq!(err.to_string(), "unknown flag \"--verbose\"");
+}
@@ -0,0 +1,73 @@
+#![cfg(any(serenity = "0_10", serenity = "0_11"))]
+#![allow(dead_code)]
+
+mod common;
+
+use common::{Ban, Config, Reset};
+use insta::assert_json_snapshot;
+use serde_json::Value;
+use serenity_commands::macros::{Command, Commands};
+use serenity_commands::serenity::builder::{CreateApplicationCommand, CreateApplicationCommands};
+#[cfg(serenity = "0_11")]
+use serenity_commands::serenity::json::hashmap_to_json_map;
+use serenity_commands::serenity::model::channel::PartialChannel;
+use serenity_commands::serenity::model::guild::Role;
+#[cfg(serenity = "0_10")]
+use serenity_commands::serenity::utils::hashmap_to_json_map;
+
+/// Announce something.
+#[derive(Command)]
+#[command(name = "announce")]
+struct Announce {
+    /// The channel to announce in.
+    #[option(channel)]
+    channel: PartialChannel,
+    /// The role to ping.
+    #[option(role)]
+    role: Option<Role>,
+    /// Delay in hours.
+    #[option(number)]
+    delay: f64,
+    /// Whether to pin the announcement.
+    #[option(boolean)]
+    pin: bool,
+}
+
+#[derive(Commands)]
+enum Commands {
+    Ban(Ban),
+    Config(Config),
+}
+
+fn register(
+    f: impl FnOnce(&mut CreateApplicationCommand) -> &mut CreateApplicationCommand,
+) -> Value {
+    let mut cmd = CreateApplicationCommand::default();
+    f(&mut cmd);
+
+    Value::Object(hashmap_to_json_map(cmd.0))
+}
+
+#[test]
+fn registers_options() {
+    assert_json_snapshot!("ban", register(Ban::register_command));
+    assert_json_snapshot!("announce", register(Announce::register_command));
+}
+
+#[test]
+fn registers_unit_commands() {
+    assert_json_snapshot!("reset", register(Reset::register_command));
+}
+
+#[test]
+fn registers_subcommands_and_groups() {
+    assert_json_snapshot!("config", register(Config::register_command));
+}
+
+#[test]
+fn registers_all_commands() {
+    let mut cmds = CreateApplicationCommands::default();
+    Commands::register_commands(&mut cmds);
+
+    assert_json_snapshot!("commands", Value::Array(cmds.0));
+}
@@ -0,0 +1,72 @@
+#![cfg(any(serenity = "0_10", serenity = "0_11"))]
+
+mod common;
+
+use common::{Config, Prefix, Reset, Set};
+use serenity_commands::macros::Command;
+use serenity_commands::serenity::model::guild::Role;
+use serenity_commands::serenity::model::user::User;
+use serenity_commands::testing;
+
+/// Ban a user.
+#[derive(Debug, PartialEq, Command)]
+#[command(name = "ban")]
+struct Ban {
+    /// The user to ban.
+    #[option(user)]
+    user: User,
+    /// The role to give instead.
+    #[option(role)]
+    role: Option<Role>,
+    /// The reason for the ban.
+    #[option(string)]
+    reason: Option<String>,
+    /// Days of messages to delete.
+    #[option(integer)]
+    days: i64,
+    /// Whether to notify the user.
+    #[option(boolean)]
+    notify: bool,
+    /// Hours until the ban is lifted.
+    #[option(number)]
+    hours: Option<f64>,
+}
+
+#[test]
+fn round_trips_options() {
+    let ban = Ban {
+        user: testing::user(1, "alice"),
+        role: Some(testing::role(2, 3, "muted")),
+        reason: None,
+        days: 7,
+        notify: true,
+        hours: Some(1.5),
+    };
+
+    let options = ban.to_options();
+
+    let names = options.iter().map(|o| &o.name[..]).collect::<Vec<_>>();
+
+    assert_eq!(names, ["user", "role", "days", "notify", "hours"]);
+    assert_eq!(Ban::parse(options).unwrap(), ban);
+}
+
+#[test]
+fn round_trips_subcommands() {
+    let config = Config::Reset(Reset);
+
+    assert_eq!(Config::parse(config.to_options()).unwrap(), config);
+}
+
+#[test]
+fn round_trips_subcommand_groups() {
+    let config = Config::Prefix(Prefix::Set(Set {
+        key: "!".to_string(),
+    }));
+
+    let options = config.to_options();
+
+    assert_eq!(options[0].name, "prefix");
+    assert_eq!(options[0].options()[0].name, "set");
+    assert_eq!(Config::parse(options).unwrap(), config);
+}
@@ -0,0 +1,306 @@
+#![cfg(serenity = "0_12")]
+
+mod common;
+
+use common::{Ban, Config, Prefix, Reset, Set};
+use serde_json::Value;
+use serenity_commands::context::CommandContext;
+use serenity_commands::help::Help;
+use serenity_commands::macros::{Command, Commands, Component, Modal};
+use serenity_commands::model::InteractionOptions;
+use serenity_commands::serenity::builder::CreateSelectMenuKind;
+use serenity_commands::serenity::model::application::{
+    CommandData,
+    ComponentInteractionData,
+    ModalInteraction,
+};
+use serenity_commands::serenity::model::id::{ChannelId, RoleId, UserId};
+use serenity_commands::testing::{self, GroupBuilder, InteractionBuilder, SubCommandBuilder};
+
+/// Announce something.
+#[derive(Debug, PartialEq, Command)]
+#[command(name = "announce")]
+struct Announce {
+    /// The channel to announce in.
+    #[option(channel)]
+    channel: ChannelId,
+    /// The role to ping.
+    #[option(role)]
+    role: Option<RoleId>,
+}
+
+#[derive(Debug, PartialEq, Commands)]
+enum AllCommands {
+    Ban(Ban),
+    Announce(Announce),
+    Config(Config),
+    Help(Help),
+}
+
+/// Echo a message.
+#[serenity_commands::command]
+async fn echo(
+    ctx: CommandContext<'_>,
+    /// The text to echo.
+    text: String,
+) -> serenity_commands::serenity::Result<()> {
+    ctx.reply_ephemeral(text).await
+}
+
+/// Warn a user.
+#[serenity_commands::command]
+async fn warn(
+    ctx: CommandContext<'_>,
+    /// The user to warn.
+    user: UserId,
+    /// The channel to warn them in.
+    channel: Option<ChannelId>,
+    /// The role to give them.
+    role: Option<RoleId>,
+) -> serenity_commands::serenity::Result<()> {
+    ctx.reply(format!("Warned {} in {:?}, giving them {:?}.", user, channel, role)).await
+}
+
+/// Generate a report.
+#[serenity_commands::command(defer)]
+async fn report(ctx: CommandContext<'_>) -> serenity_commands::serenity::Result<()> {
+    ctx.reply("The report is ready.").await
+}
+
+#[derive(Debug, Commands)]
+#[commands(dispatch)]
+enum Dispatched {
+    Echo(Echo),
+    Warn(Warn),
+    Report(Report),
+}
+
+#[derive(Debug, PartialEq, Component)]
+enum Moderation {
+    #[component(id = "warn", placeholder = "Pick the users to warn")]
+    Warn {
+        reason: String,
+        #[component(values)]
+        users: Vec<String>,
+    },
+}
+
+/// Send feedback
+#[derive(Debug, PartialEq, Modal)]
+#[modal(id = "feedback")]
+struct Feedback {
+    /// Subject
+    #[input(short)]
+    subject: String,
+}
+
+#[test]
+fn creates_commands() {
+    let command = serde_json::to_value(Announce::create_command()).unwrap();
+
+    assert_eq!(command["name"], "announce");
+    assert_eq!(command["description"], "Announce something.");
+
+    let options = command["options"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|o| (o["name"].as_str().unwrap(), o["type"].as_u64().unwrap(), o["required"] == true))
+        .collect::<Vec<_>>();
+
+    assert_eq!(options, [("channel", 7, true), ("role", 8, false)]);
+}
+
+#[test]
+fn creates_nested_commands() {
+    let command = serde_json::to_value(Config::create_command()).unwrap();
+
+    assert_eq!(command["default_member_permissions"], "32");
+    assert_eq!(command["dm_permission"], false);
+
+    let group = &command["options"][1];
+    assert_eq!(group["type"], 2);
+    assert_eq!(group["name"], "prefix");
+    assert_eq!(group["options"][0]["name"], "set");
+    assert_eq!(group["options"][0]["options"][0]["name"], "key");
+}
+
+#[test]
+fn parses_ids() {
+    let interaction = InteractionBuilder::command("ban")
+        .guild(1)
+        .option("user", testing::user(2, "alice"))
+        .build();
+
+    assert_eq!(
+        AllCommands::parse(interaction).unwrap(),
+        AllCommands::Ban(Ban {
+            user: UserId::new(2),
+            reason: None,
+            days: None,
+        })
+    );
+
+    let interaction = InteractionBuilder::command("announce")
+        .guild(1)
+        .option("channel", testing::channel(3, "news"))
+        .option("role", testing::role(1, 4, "muted"))
+        .build();
+
+    assert_eq!(
+        AllCommands::parse(interaction).unwrap(),
+        AllCommands::Announce(Announce {
+            channel: ChannelId::new(3),
+            role: Some(RoleId::new(4)),
+        })
+    );
+}
+
+#[test]
+fn converts_interaction_options() {
+    let options = InteractionBuilder::command("announce")
+        .option("channel", testing::channel(3, "news"))
+        .option("role", testing::role(1, 4, "muted"))
+        .build_options();
+
+    let data = CommandData::try_from(&options).unwrap();
+
+    assert_eq!(InteractionOptions::try_from(&data).unwrap(), options);
+    assert_eq!(Announce::parse_command(data).unwrap(), Announce {
+        channel: ChannelId::new(3),
+        role: Some(RoleId::new(4)),
+    });
+}
+
+#[test]
+fn parses_subcommand_groups() {
+    let interaction = InteractionBuilder::command("config")
+        .group(
+            GroupBuilder::new("prefix")
+                .subcommand(SubCommandBuilder::new("set").option("key", "!")),
+        )
+        .build();
+
+    assert_eq!(
+        AllCommands::parse(interaction).unwrap(),
+        AllCommands::Config(Config::Prefix(Prefix::Set(Set {
+            key: "!".to_string()
+        })))
+    );
+}
+
+#[test]
+fn round_trips_options() {
+    let ban = Ban {
+        user: UserId::new(2),
+        reason: None,
+        days: Some(7),
+    };
+
+    assert_eq!(Ban::parse(ban.to_options()).unwrap(), ban);
+
+    let announce = Announce {
+        channel: ChannelId::new(3),
+        role: Some(RoleId::new(4)),
+    };
+
+    assert_eq!(Announce::parse(announce.to_options()).unwrap(), announce);
+
+    let config = Config::Prefix(Prefix::Reset(Reset));
+
+    assert_eq!(Config::parse(config.to_options()).unwrap(), config);
+}
+
+#[test]
+fn creates_modals() {
+    let response = serde_json::to_value(Feedback::create_modal()).unwrap();
+
+    assert_eq!(response["type"], 9);
+    assert_eq!(response["data"]["custom_id"], "feedback");
+    assert_eq!(response["data"]["components"][0]["components"][0]["custom_id"], "subject");
+}
+
+#[test]
+fn parses_entity_select_menus() {
+    let data: ComponentInteractionData = serde_json::from_value(serde_json::json!({
+        "custom_id": "warn:spam",
+        "component_type": 5,
+        "values": ["1", "2"],
+    }))
+    .unwrap();
+
+    assert_eq!(Moderation::parse_data(data).unwrap(), Moderation::Warn {
+        reason: "spam".to_string(),
+        users: vec!["1".to_string(), "2".to_string()],
+    });
+}
+
+#[test]
+fn creates_select_menus() {
+    let warn = Moderation::Warn {
+        reason: "spam".to_string(),
+        users: Vec::new(),
+    };
+
+    let kind = CreateSelectMenuKind::User {
+        default_users: None,
+    };
+    let menu = serde_json::to_value(warn.create_select_menu(kind).unwrap()).unwrap();
+
+    assert_eq!(menu["custom_id"], "warn:spam");
+    assert_eq!(menu["placeholder"], "Pick the users to warn");
+}
+
+#[test]
+fn parses_modal_interactions() {
+    let interaction: ModalInteraction =
+        serde_json::from_str(include_str!("fixtures/modal_submit.json")).unwrap();
+
+    assert_eq!(Feedback::parse(&interaction).unwrap(), Feedback {
+        subject: "Bug".to_string(),
+    });
+}
+
+#[test]
+fn creates_help_embeds() {
+    let help = Help {
+        command: Some("config prefix".to_string()),
+        page: None,
+    };
+
+    let embed: Value = serde_json::to_value(help.create_embed::<AllCommands>()).unwrap();
+
+    assert_eq!(embed["title"], "/config prefix");
+    assert_eq!(embed["fields"][0]["name"], "Subcommands");
+}
+
+#[test]
+fn parses_commands_taking_contexts() {
+    let interaction = InteractionBuilder::command("echo").option("text", "hi").build();
+
+    assert!(matches!(
+        Dispatched::parse(interaction).unwrap(),
+        Dispatched::Echo(Echo { text }) if text == "hi"
+    ));
+}
+
+#[test]
+fn infers_ids_of_commands_taking_contexts() {
+    let command = serde_json::to_value(Warn::create_command()).unwrap();
+    let kinds =
+        command["options"].as_array().unwrap().iter().map(|o| &o["type"]).collect::<Vec<_>>();
+
+    assert_eq!(kinds, [6, 7, 8]);
+
+    let interaction = InteractionBuilder::command("warn")
+        .guild(1)
+        .option("user", testing::user(2, "alice"))
+        .option("role", testing::role(1, 4, "warned"))
+        .build();
+
+    assert!(matches!(
+        Dispatched::parse(interaction).unwrap(),
+        Dispatched::Warn(Warn { user, channel: None, role: Some(role) })
+            if user == UserId::new(2) && role == RoleId::new(4)
+    ));
+}
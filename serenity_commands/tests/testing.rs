@@ -0,0 +1,169 @@
+#![cfg(any(serenity = "0_10", serenity = "0_11"))]
+
+mod common;
+
+use common::{Ban, Config, Prefix, Reset, Set};
+use serenity_commands::error::ParseError;
+use serenity_commands::macros::{Command, Commands};
+use serenity_commands::serenity::model::channel::PartialChannel;
+use serenity_commands::serenity::model::guild::Role;
+use serenity_commands::serenity::model::user::User;
+use serenity_commands::testing::{self, GroupBuilder, InteractionBuilder, SubCommandBuilder};
+
+/// Announce something.
+#[derive(Debug, Command)]
+#[command(name = "announce")]
+struct Announce {
+    /// The channel to announce in.
+    #[option(channel)]
+    channel: PartialChannel,
+    /// The role to ping.
+    #[option(role)]
+    role: Role,
+    /// Delay in hours.
+    #[option(number)]
+    delay: f64,
+}
+
+#[derive(Debug, Commands)]
+enum Commands {
+    Ban(Ban),
+    Announce(Announce),
+    Config(Config),
+}
+
+#[test]
+fn parses_options() {
+    let user = testing::user(4, "alice");
+
+    let interaction = InteractionBuilder::command("ban")
+        .guild(1)
+        .option("user", user.clone())
+        .option("reason", "spam")
+        .build();
+
+    let ban = match Commands::parse(interaction).unwrap() {
+        Commands::Ban(ban) => ban,
+        cmd => panic!("unexpected command {:?}", cmd),
+    };
+
+    assert_eq!(ban.user, user);
+    assert_eq!(ban.reason.as_deref(), Some("spam"));
+    assert_eq!(ban.days, None);
+}
+
+#[test]
+fn parses_resolved_options() {
+    let interaction = InteractionBuilder::command("announce")
+        .guild(1)
+        .option("channel", testing::channel(2, "news"))
+        .option("role", testing::role(1, 3, "everyone"))
+        .option("delay", 1.5)
+        .build();
+
+    match Commands::parse(interaction).unwrap() {
+        Commands::Announce(Announce {
+            channel,
+            role,
+            delay,
+        }) => {
+            assert_eq!(channel.id.0, 2);
+            assert_eq!(role.name, "everyone");
+            assert_eq!(delay, 1.5);
+        },
+        cmd => panic!("unexpected command {:?}", cmd),
+    }
+}
+
+#[test]
+fn parses_subcommands() {
+    let interaction =
+        InteractionBuilder::command("config").subcommand(SubCommandBuilder::new("reset")).build();
+
+    assert!(matches!(Commands::parse(interaction), Ok(Commands::Config(Config::Reset(Reset)))));
+}
+
+#[test]
+fn parses_subcommand_groups() {
+    let interaction = InteractionBuilder::command("config")
+        .group(
+            GroupBuilder::new("prefix")
+                .subcommand(SubCommandBuilder::new("set").option("key", "!")),
+        )
+        .build();
+
+    match Commands::parse(interaction).unwrap() {
+        Commands::Config(Config::Prefix(Prefix::Set(Set {
+            key,
+        }))) => assert_eq!(key, "!"),
+        cmd => panic!("unexpected command {:?}", cmd),
+    }
+}
+
+#[test]
+fn parses_raw_payloads() {
+    let payload = InteractionBuilder::command("ban").option("user", User::default()).to_json();
+
+    assert!(matches!(Commands::parse_json(&payload), Ok(Commands::Ban(_))));
+    assert!(matches!(Commands::from_slice(payload.to_string().as_bytes()), Ok(Commands::Ban(_))));
+}
+
+#[test]
+fn rejects_missing_options() {
+    let interaction = InteractionBuilder::command("ban").option("reason", "spam").build();
+
+    assert!(matches!(Commands::parse(interaction), Err(ParseError::MissingOption("user"))));
+}
+
+#[test]
+fn rejects_unknown_options() {
+    let interaction = InteractionBuilder::command("ban")
+        .option("user", User::default())
+        .option("silent", true)
+        .build();
+
+    assert!(
+        matches!(Commands::parse(interaction), Err(ParseError::UnknownOption(o)) if o == "silent")
+    );
+}
+
+#[test]
+fn rejects_invalid_types() {
+    let interaction = InteractionBuilder::command("ban")
+        .option("user", User::default())
+        .option("delete-days", "seven")
+        .build();
+
+    assert!(matches!(Commands::parse(interaction), Err(ParseError::InvalidType(_))));
+}
+
+#[test]
+fn rejects_unknown_commands() {
+    let interaction = InteractionBuilder::command("kick").build();
+
+    assert!(
+        matches!(Commands::parse(interaction), Err(ParseError::UnknownCommand(c)) if c == "kick")
+    );
+}
+
+#[test]
+fn rejects_unknown_subcommands() {
+    let interaction =
+        InteractionBuilder::command("config").subcommand(SubCommandBuilder::new("unset")).build();
+
+    assert!(
+        matches!(Commands::parse(interaction), Err(ParseError::UnknownSubCommand(c)) if c == "unset")
+    );
+}
+
+#[test]
+fn rejects_missing_subcommands() {
+    let payload = InteractionBuilder::command("config").to_json();
+
+    assert!(matches!(Commands::parse_json(&payload), Err(ParseError::MissingName)));
+
+    let payload =
+        InteractionBuilder::command("config").group(GroupBuilder::new("prefix")).to_json();
+
+    assert!(matches!(Commands::parse_json(&payload), Err(ParseError::MissingName)));
+}
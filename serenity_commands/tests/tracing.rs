@@ -0,0 +1,192 @@
+#![cfg(any(serenity = "0_10", serenity = "0_11"))]
+#![allow(dead_code)]
+
+mod common;
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+use common::Config;
+use serenity_commands::dispatch::Dispatcher;
+use serenity_commands::error::ParseError;
+use serenity_commands::macros::Commands;
+use serenity_commands::serenity::client::Context;
+use serenity_commands::serenity::model::interactions::application_command::ApplicationCommandInteraction;
+use serenity_commands::testing::{self, GroupBuilder, InteractionBuilder, SubCommandBuilder};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+#[derive(Debug, Commands)]
+enum AllCommands {
+    Config(Config),
+}
+
+/// Ping the bot.
+#[serenity_commands::command]
+async fn ping(_ctx: &Context, _interaction: &ApplicationCommandInteraction) -> &'static str {
+    "pong"
+}
+
+#[derive(Debug, Commands)]
+#[commands(dispatch)]
+enum Dispatched {
+    Ping(Ping),
+}
+
+/// A span or an event, with its fields formatted.
+#[derive(Debug, Clone, PartialEq)]
+struct Recorded {
+    name: &'static str,
+    fields: BTreeMap<&'static str, String>,
+}
+
+/// An event, along with the span it was emitted in.
+#[derive(Debug, Clone)]
+struct RecordedEvent {
+    span: Option<Recorded>,
+    event: Recorded,
+}
+
+/// Records the events emitted, along with the span they were emitted in.
+#[derive(Default, Clone)]
+struct Recorder {
+    spans: Arc<Mutex<Vec<Recorded>>>,
+    entered: Arc<Mutex<Vec<u64>>>,
+    events: Arc<Mutex<Vec<RecordedEvent>>>,
+}
+
+struct Fields<'a>(&'a mut BTreeMap<&'static str, String>);
+
+impl Visit for Fields<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.insert(field.name(), format!("{:?}", value));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = BTreeMap::new();
+        span.record(&mut Fields(&mut fields));
+
+        let mut spans = self.spans.lock().unwrap();
+        spans.push(Recorded {
+            name: span.metadata().name(),
+            fields,
+        });
+
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut spans = self.spans.lock().unwrap();
+        values.record(&mut Fields(&mut spans[span.into_u64() as usize - 1].fields));
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = BTreeMap::new();
+        event.record(&mut Fields(&mut fields));
+
+        let span = self
+            .entered
+            .lock()
+            .unwrap()
+            .last()
+            .map(|id| self.spans.lock().unwrap()[*id as usize - 1].clone());
+
+        self.events.lock().unwrap().push(RecordedEvent {
+            span,
+            event: Recorded {
+                name: event.metadata().name(),
+                fields,
+            },
+        });
+    }
+
+    fn enter(&self, span: &Id) {
+        self.entered.lock().unwrap().push(span.into_u64());
+    }
+
+    fn exit(&self, _span: &Id) {
+        self.entered.lock().unwrap().pop();
+    }
+}
+
+impl Recorder {
+    /// Returns the events with `message`.
+    fn find(&self, message: &str) -> Vec<RecordedEvent> {
+        let events = self.events.lock().unwrap();
+
+        events
+            .iter()
+            .filter(|e| e.event.fields.get("message").map(|m| &m[..]) == Some(message))
+            .cloned()
+            .collect()
+    }
+}
+
+#[test]
+fn traces_parse_errors() {
+    let recorder = Recorder::default();
+
+    let interaction = InteractionBuilder::command("config")
+        .guild(3)
+        .channel(4)
+        .user(testing::user(5, "alice"))
+        .group(
+            GroupBuilder::new("prefix").subcommand(SubCommandBuilder::new("set").option("key", 1)),
+        )
+        .build();
+
+    let result =
+        tracing::subscriber::with_default(recorder.clone(), || AllCommands::parse(interaction));
+
+    assert!(matches!(result, Err(ParseError::InvalidType(_))));
+
+    let events = recorder.find("failed to parse command");
+    assert_eq!(events.len(), 1);
+
+    let span = events[0].span.as_ref().unwrap();
+
+    assert_eq!(span.name, "parse_command");
+    assert_eq!(span.fields["path"], "config prefix set");
+    assert_eq!(span.fields["guild_id"], "3");
+    assert_eq!(span.fields["channel_id"], "4");
+    assert_eq!(span.fields["user_id"], "5");
+
+    assert_eq!(events[0].event.fields["error"], result.unwrap_err().to_string());
+}
+
+#[tokio::test]
+async fn traces_dispatched_commands() {
+    let recorder = Recorder::default();
+    let _default = tracing::subscriber::set_default(recorder.clone());
+
+    let ctx = testing::context();
+    let interaction = InteractionBuilder::command("ping").user(testing::user(5, "alice")).build();
+
+    assert_eq!(Dispatcher::new().dispatch::<Dispatched>(&ctx, &interaction).await.unwrap(), "pong");
+
+    let events = recorder.find("ran command");
+    assert_eq!(events.len(), 1);
+
+    let span = events[0].span.as_ref().unwrap();
+
+    assert_eq!(span.name, "dispatch_command");
+    assert_eq!(span.fields["path"], "ping");
+    assert_eq!(span.fields["user_id"], "5");
+    assert!(events[0].event.fields.contains_key("elapsed"));
+
+    assert_eq!(recorder.find("parsed command").len(), 1);
+}
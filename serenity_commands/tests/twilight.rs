@@ -0,0 +1,270 @@
+#![cfg(twilight)]
+
+mod common;
+
+use common::{Ban, Config, Prefix, Reset, Set};
+
+use serde_json::json;
+use serenity_commands::error::ParseError;
+use serenity_commands::help::Help;
+use serenity_commands::macros::{Command, Commands};
+use serenity_commands::model::InteractionOptions;
+use serenity_commands::twilight_model::application::command::{CommandOptionType, CommandType};
+use serenity_commands::twilight_model::application::interaction::application_command::{
+    CommandData,
+    CommandDataOption,
+    CommandOptionValue,
+};
+use serenity_commands::twilight_model::id::marker::ChannelMarker;
+use serenity_commands::twilight_model::id::Id;
+
+/// Announce something.
+#[derive(Debug, PartialEq, Command)]
+#[command(name = "announce", prefix)]
+struct Announce {
+    /// The channel to announce in.
+    #[option(channel)]
+    channel: Id<ChannelMarker>,
+    /// Hours until the announcement.
+    #[option(number)]
+    hours: Option<f64>,
+}
+
+#[derive(Debug, PartialEq, Commands)]
+enum AllCommands {
+    Ban(Ban),
+    Config(Config),
+    Help(Help),
+}
+
+fn data(name: &str, options: Vec<CommandDataOption>) -> CommandData {
+    CommandData {
+        guild_id: None,
+        id: Id::new(1),
+        name: name.to_string(),
+        kind: CommandType::ChatInput,
+        options,
+        resolved: None,
+        target_id: None,
+    }
+}
+
+fn option(name: &str, value: CommandOptionValue) -> CommandDataOption {
+    CommandDataOption {
+        name: name.to_string(),
+        value,
+    }
+}
+
+#[test]
+fn creates_commands() {
+    let commands = AllCommands::create_commands();
+
+    assert_eq!(commands.len(), 3);
+
+    let ban = &commands[0];
+    assert_eq!(ban.name, "ban");
+    assert_eq!(ban.description, "Ban a user.");
+    assert_eq!(ban.kind, CommandType::ChatInput);
+
+    let options = ban
+        .options
+        .iter()
+        .map(|o| (&o.name[..], o.kind, o.required.unwrap_or(false)))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        options,
+        [
+            ("user", CommandOptionType::User, true),
+            ("reason", CommandOptionType::String, false),
+            ("delete-days", CommandOptionType::Integer, false),
+        ]
+    );
+}
+
+#[test]
+fn creates_nested_commands() {
+    let config = Config::create_command();
+
+    let group = &config.options[1];
+    assert_eq!(group.kind, CommandOptionType::SubCommandGroup);
+    assert_eq!(group.name, "prefix");
+
+    let set = &group.options.as_ref().unwrap()[0];
+    assert_eq!(set.kind, CommandOptionType::SubCommand);
+    assert_eq!(set.options.as_ref().unwrap()[0].name, "key");
+}
+
+#[test]
+fn parses_command_data() {
+    let data = data(
+        "ban",
+        vec![
+            option("user", CommandOptionValue::User(Id::new(2))),
+            option("delete-days", CommandOptionValue::Integer(7)),
+        ],
+    );
+
+    assert_eq!(
+        AllCommands::parse(data).unwrap(),
+        AllCommands::Ban(Ban {
+            user: Id::new(2),
+            reason: None,
+            days: Some(7),
+        })
+    );
+}
+
+#[test]
+fn parses_subcommand_groups() {
+    let data = data(
+        "config",
+        vec![option(
+            "prefix",
+            CommandOptionValue::SubCommandGroup(vec![option(
+                "set",
+                CommandOptionValue::SubCommand(vec![option(
+                    "key",
+                    CommandOptionValue::String("!".to_string()),
+                )]),
+            )]),
+        )],
+    );
+
+    assert_eq!(
+        AllCommands::parse(data).unwrap(),
+        AllCommands::Config(Config::Prefix(Prefix::Set(Set {
+            key: "!".to_string()
+        })))
+    );
+}
+
+#[test]
+fn rejects_invalid_types() {
+    let data = data("ban", vec![option("user", CommandOptionValue::Boolean(true))]);
+
+    assert!(matches!(
+        AllCommands::parse(data),
+        Err(ParseError::InvalidType(CommandOptionType::User))
+    ));
+}
+
+#[test]
+fn parses_interaction_payloads() {
+    let payload = json!({
+        "id": "1",
+        "application_id": "1",
+        "type": 2,
+        "token": "token",
+        "version": 1,
+        "channel_id": "1",
+        "locale": "en-US",
+        "authorizing_integration_owners": {},
+        "data": {
+            "id": "1",
+            "name": "ban",
+            "type": 1,
+            "options": [{ "name": "user", "type": 6, "value": "2" }],
+        },
+    });
+
+    assert_eq!(
+        AllCommands::parse_json(&payload).unwrap(),
+        AllCommands::Ban(Ban {
+            user: Id::new(2),
+            reason: None,
+            days: None,
+        })
+    );
+}
+
+#[test]
+fn converts_interaction_options() {
+    let options: InteractionOptions = serde_json::from_value(json!({
+        "name": "ban",
+        "options": [{ "name": "user", "type": 6, "value": "2" }],
+    }))
+    .unwrap();
+
+    let data = CommandData::try_from(&options).unwrap();
+
+    assert_eq!(InteractionOptions::try_from(&data).unwrap(), options);
+    assert_eq!(
+        AllCommands::parse(data).unwrap(),
+        AllCommands::Ban(Ban {
+            user: Id::new(2),
+            reason: None,
+            days: None,
+        })
+    );
+}
+
+#[test]
+fn creates_commands_from_definitions() {
+    for (definition, command) in
+        AllCommands::definitions().into_iter().zip(AllCommands::create_commands())
+    {
+        assert_eq!(
+            serenity_commands::twilight_model::application::command::Command::from(definition),
+            command
+        );
+    }
+
+    let config = Config::create_command();
+    assert_eq!(config.default_member_permissions.map(|p| p.bits()), Some(1 << 5));
+}
+
+#[test]
+fn round_trips_options() {
+    let ban = Ban {
+        user: Id::new(2),
+        reason: Some("spam".to_string()),
+        days: None,
+    };
+
+    assert_eq!(Ban::parse(ban.to_options()).unwrap(), ban);
+
+    let announce = Announce {
+        channel: Id::new(3),
+        hours: Some(1.5),
+    };
+
+    assert_eq!(Announce::parse(announce.to_options()).unwrap(), announce);
+
+    let config = Config::Prefix(Prefix::Reset(Reset));
+
+    assert_eq!(Config::parse(config.to_options()).unwrap(), config);
+}
+
+#[test]
+fn parses_messages() {
+    assert_eq!(
+        Ban::parse_message("ban <@2> spam").unwrap(),
+        Ban {
+            user: Id::new(2),
+            reason: Some("spam".to_string()),
+            days: None,
+        }
+    );
+    assert_eq!(
+        Announce::parse_message("announce <#3>").unwrap(),
+        Announce {
+            channel: Id::new(3),
+            hours: None,
+        }
+    );
+}
+
+#[test]
+fn creates_help_embeds() {
+    let help = Help {
+        command: Some("config prefix".to_string()),
+        page: None,
+    };
+
+    let embed = help.create_embed::<AllCommands>();
+
+    assert_eq!(embed.title.as_deref(), Some("/config prefix"));
+    assert_eq!(embed.fields[0].name, "Subcommands");
+}
@@ -0,0 +1,8 @@
+// The expected errors refer to the paths of Serenity 0.10.
+#![cfg(serenity = "0_10")]
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,8 @@
+use serenity_commands::macros::Command;
+
+/// Ping.
+#[derive(Command)]
+#[command = "ping"]
+struct Ping;
+
+fn main() {}
@@ -0,0 +1,9 @@
+use serenity_commands::macros::Command;
+
+/// Ping.
+#[doc = "Pong."]
+#[derive(Command)]
+#[command(name = "ping")]
+struct Ping;
+
+fn main() {}
@@ -0,0 +1,8 @@
+use serenity_commands::macros::Command;
+
+/// Ping the bot.
+#[derive(Command)]
+#[command(name = "ping", prefix, prefix)]
+struct Ping;
+
+fn main() {}
@@ -0,0 +1,9 @@
+/// Generate a report.
+#[serenity_commands::command(defer(hidden))]
+async fn report(
+    _ctx: &serenity_commands::serenity::client::Context,
+    _interaction: &serenity_commands::serenity::model::interactions::application_command::ApplicationCommandInteraction,
+) {
+}
+
+fn main() {}
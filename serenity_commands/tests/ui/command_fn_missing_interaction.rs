@@ -0,0 +1,5 @@
+/// Ping the bot.
+#[serenity_commands::command]
+async fn ping(_ctx: &serenity_commands::serenity::client::Context) {}
+
+fn main() {}
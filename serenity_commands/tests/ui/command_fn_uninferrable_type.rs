@@ -0,0 +1,11 @@
+/// Ping the bot.
+#[serenity_commands::command]
+async fn ping(
+    _ctx: &serenity_commands::serenity::client::Context,
+    _interaction: &serenity_commands::serenity::model::interactions::application_command::ApplicationCommandInteraction,
+    /// How many times to ping.
+    times: u8,
+) {
+}
+
+fn main() {}
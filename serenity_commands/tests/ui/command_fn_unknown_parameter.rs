@@ -0,0 +1,9 @@
+/// Ping the bot.
+#[serenity_commands::command(name = "ping", prefix)]
+async fn ping(
+    _ctx: &serenity_commands::serenity::client::Context,
+    _interaction: &serenity_commands::serenity::model::interactions::application_command::ApplicationCommandInteraction,
+) {
+}
+
+fn main() {}
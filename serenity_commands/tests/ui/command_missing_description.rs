@@ -0,0 +1,7 @@
+use serenity_commands::macros::Command;
+
+#[derive(Command)]
+#[command(name = "ping")]
+struct Ping;
+
+fn main() {}
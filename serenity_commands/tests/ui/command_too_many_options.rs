@@ -0,0 +1,87 @@
+use serenity_commands::macros::Command;
+
+/// Ping.
+#[derive(Command)]
+#[command(name = "ping")]
+struct Ping {
+    /// Option 0.
+    #[option(integer)]
+    o0: i64,
+    /// Option 1.
+    #[option(integer)]
+    o1: i64,
+    /// Option 2.
+    #[option(integer)]
+    o2: i64,
+    /// Option 3.
+    #[option(integer)]
+    o3: i64,
+    /// Option 4.
+    #[option(integer)]
+    o4: i64,
+    /// Option 5.
+    #[option(integer)]
+    o5: i64,
+    /// Option 6.
+    #[option(integer)]
+    o6: i64,
+    /// Option 7.
+    #[option(integer)]
+    o7: i64,
+    /// Option 8.
+    #[option(integer)]
+    o8: i64,
+    /// Option 9.
+    #[option(integer)]
+    o9: i64,
+    /// Option 10.
+    #[option(integer)]
+    o10: i64,
+    /// Option 11.
+    #[option(integer)]
+    o11: i64,
+    /// Option 12.
+    #[option(integer)]
+    o12: i64,
+    /// Option 13.
+    #[option(integer)]
+    o13: i64,
+    /// Option 14.
+    #[option(integer)]
+    o14: i64,
+    /// Option 15.
+    #[option(integer)]
+    o15: i64,
+    /// Option 16.
+    #[option(integer)]
+    o16: i64,
+    /// Option 17.
+    #[option(integer)]
+    o17: i64,
+    /// Option 18.
+    #[option(integer)]
+    o18: i64,
+    /// Option 19.
+    #[option(integer)]
+    o19: i64,
+    /// Option 20.
+    #[option(integer)]
+    o20: i64,
+    /// Option 21.
+    #[option(integer)]
+    o21: i64,
+    /// Option 22.
+    #[option(integer)]
+    o22: i64,
+    /// Option 23.
+    #[option(integer)]
+    o23: i64,
+    /// Option 24.
+    #[option(integer)]
+    o24: i64,
+    /// Option 25.
+    #[option(integer)]
+    o25: i64,
+}
+
+fn main() {}
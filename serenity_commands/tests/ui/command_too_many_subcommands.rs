@@ -0,0 +1,66 @@
+use serenity_commands::macros::Command;
+
+/// Reset.
+#[derive(Command)]
+#[command(name = "reset")]
+struct Reset;
+
+/// Configure.
+#[derive(Command)]
+#[command(name = "config")]
+enum Config {
+    #[option(subcommand)]
+    S0(Reset),
+    #[option(subcommand)]
+    S1(Reset),
+    #[option(subcommand)]
+    S2(Reset),
+    #[option(subcommand)]
+    S3(Reset),
+    #[option(subcommand)]
+    S4(Reset),
+    #[option(subcommand)]
+    S5(Reset),
+    #[option(subcommand)]
+    S6(Reset),
+    #[option(subcommand)]
+    S7(Reset),
+    #[option(subcommand)]
+    S8(Reset),
+    #[option(subcommand)]
+    S9(Reset),
+    #[option(subcommand)]
+    S10(Reset),
+    #[option(subcommand)]
+    S11(Reset),
+    #[option(subcommand)]
+    S12(Reset),
+    #[option(subcommand)]
+    S13(Reset),
+    #[option(subcommand)]
+    S14(Reset),
+    #[option(subcommand)]
+    S15(Reset),
+    #[option(subcommand)]
+    S16(Reset),
+    #[option(subcommand)]
+    S17(Reset),
+    #[option(subcommand)]
+    S18(Reset),
+    #[option(subcommand)]
+    S19(Reset),
+    #[option(subcommand)]
+    S20(Reset),
+    #[option(subcommand)]
+    S21(Reset),
+    #[option(subcommand)]
+    S22(Reset),
+    #[option(subcommand)]
+    S23(Reset),
+    #[option(subcommand)]
+    S24(Reset),
+    #[option(subcommand)]
+    S25(Reset),
+}
+
+fn main() {}
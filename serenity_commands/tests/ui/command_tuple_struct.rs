@@ -0,0 +1,8 @@
+use serenity_commands::macros::Command;
+
+/// Ping.
+#[derive(Command)]
+#[command(name = "ping")]
+struct Ping(i64);
+
+fn main() {}
@@ -0,0 +1,10 @@
+use serenity_commands::macros::Command;
+
+/// Ping.
+#[derive(Command)]
+#[command(name = "ping")]
+union Ping {
+    n: i64,
+}
+
+fn main() {}
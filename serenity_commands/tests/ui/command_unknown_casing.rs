@@ -0,0 +1,8 @@
+use serenity_commands::macros::Command;
+
+/// Ping.
+#[derive(Command)]
+#[command(rename_all = "camelCase")]
+struct Ping;
+
+fn main() {}
@@ -0,0 +1,8 @@
+use serenity_commands::macros::Command;
+
+/// Ping.
+#[derive(Command)]
+#[command(name = "ping", hidden)]
+struct Ping;
+
+fn main() {}
@@ -0,0 +1,13 @@
+use serenity_commands::macros::Command;
+
+/// Ban a user.
+#[derive(Command)]
+#[command(default_member_permissions = "BAN_MEMBERS | BAN_USERS")]
+struct Ban;
+
+/// Kick a user.
+#[derive(Command)]
+#[command(default_member_permissions = "KICK MEMBERS")]
+struct Kick;
+
+fn main() {}
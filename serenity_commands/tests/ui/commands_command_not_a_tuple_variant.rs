@@ -0,0 +1,13 @@
+use serenity_commands::macros::{Command, Commands};
+
+/// Ping.
+#[derive(Command)]
+#[command(name = "ping")]
+struct Ping;
+
+#[derive(Commands)]
+enum Commands {
+    Ping,
+}
+
+fn main() {}
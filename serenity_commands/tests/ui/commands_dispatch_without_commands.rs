@@ -0,0 +1,7 @@
+use serenity_commands::macros::Commands;
+
+#[derive(Commands)]
+#[commands(dispatch)]
+enum AllCommands {}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use serenity_commands::macros::Commands;
+
+#[derive(Commands)]
+struct Commands;
+
+fn main() {}
@@ -0,0 +1,7 @@
+use serenity_commands::macros::Commands;
+
+#[derive(Commands)]
+#[commands(prefix, suffix)]
+enum AllCommands {}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use serenity_commands::macros::Component;
+
+#[derive(Component)]
+enum Poll {
+    #[component = "refresh"]
+    Refresh,
+}
+
+fn main() {}
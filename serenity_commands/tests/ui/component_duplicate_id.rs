@@ -0,0 +1,11 @@
+use serenity_commands::macros::Component;
+
+#[derive(Component)]
+enum Poll {
+    #[component(id = "refresh")]
+    Refresh,
+    #[component(id = "refresh")]
+    Reload,
+}
+
+fn main() {}
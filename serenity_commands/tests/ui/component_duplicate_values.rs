@@ -0,0 +1,14 @@
+use serenity_commands::macros::Component;
+
+#[derive(Component)]
+enum Poll {
+    #[component(id = "pick")]
+    Pick {
+        #[component(values)]
+        options: Vec<String>,
+        #[component(values)]
+        others: Vec<String>,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use serenity_commands::macros::Component;
+
+#[derive(Component)]
+enum Poll {
+    #[component(id = "close")]
+    Close(#[component(hidden)] u64),
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use serenity_commands::macros::Component;
+
+#[derive(Component)]
+enum Poll {
+    #[component(id = "poll:refresh")]
+    Refresh,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use serenity_commands::macros::Component;
+
+#[derive(Component)]
+struct Refresh;
+
+fn main() {}
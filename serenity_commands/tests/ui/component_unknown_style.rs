@@ -0,0 +1,9 @@
+use serenity_commands::macros::Component;
+
+#[derive(Component)]
+enum Poll {
+    #[component(id = "refresh", style = "link")]
+    Refresh,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use serenity_commands::macros::Command;
+
+#[derive(Command)]
+#[command(name = "ping")]
+#[doc(hidden)]
+struct Ping;
+
+fn main() {}
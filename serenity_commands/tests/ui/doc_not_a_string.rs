@@ -0,0 +1,8 @@
+use serenity_commands::macros::Command;
+
+#[derive(Command)]
+#[command(name = "ping")]
+#[doc = 1]
+struct Ping;
+
+fn main() {}
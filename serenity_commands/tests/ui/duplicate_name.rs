@@ -0,0 +1,8 @@
+use serenity_commands::macros::Command;
+
+/// Ping.
+#[derive(Command)]
+#[command(name = "ping", name = "pong")]
+struct Ping;
+
+fn main() {}
@@ -0,0 +1,15 @@
+use serenity_commands::macros::{Command, Group};
+
+/// Reset.
+#[derive(Command)]
+#[command(name = "reset")]
+struct Reset;
+
+/// Prefix.
+#[derive(Group)]
+#[group = "prefix"]
+enum Prefix {
+    Reset(Reset),
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use serenity_commands::macros::{Command, Group};
+
+/// Reset.
+#[derive(Command)]
+struct Reset;
+
+/// Prefix.
+#[derive(Group)]
+#[group(rename_all = "kebab-case", rename_all = "snake_case")]
+enum Prefix {
+    Reset(Reset),
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use serenity_commands::macros::{Command, Group};
+
+/// Reset.
+#[derive(Command)]
+#[command(name = "reset")]
+struct Reset;
+
+/// Prefix.
+#[doc = "Prefixes."]
+#[derive(Group)]
+#[group(name = "prefix")]
+enum Prefix {
+    Reset(Reset),
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use serenity_commands::macros::{Command, Group};
+
+/// Reset.
+#[derive(Command)]
+#[command(name = "reset")]
+struct Reset;
+
+#[derive(Group)]
+#[group(name = "prefix")]
+enum Prefix {
+    Reset(Reset),
+}
+
+fn main() {}
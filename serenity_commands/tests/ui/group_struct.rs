@@ -0,0 +1,8 @@
+use serenity_commands::macros::Group;
+
+/// Prefix.
+#[derive(Group)]
+#[group(name = "prefix")]
+struct Prefix;
+
+fn main() {}
@@ -0,0 +1,15 @@
+use serenity_commands::macros::{Command, Group};
+
+/// Reset.
+#[derive(Command)]
+#[command(name = "reset")]
+struct Reset;
+
+/// Prefix.
+#[derive(Group)]
+#[group(name = "prefix")]
+enum Prefix {
+    Reset { reset: Reset },
+}
+
+fn main() {}
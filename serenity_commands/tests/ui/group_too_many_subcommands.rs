@@ -0,0 +1,40 @@
+use serenity_commands::macros::{Command, Group};
+
+/// Reset.
+#[derive(Command)]
+#[command(name = "reset")]
+struct Reset;
+
+/// Prefix.
+#[derive(Group)]
+#[group(name = "prefix")]
+enum Prefix {
+    S0(Reset),
+    S1(Reset),
+    S2(Reset),
+    S3(Reset),
+    S4(Reset),
+    S5(Reset),
+    S6(Reset),
+    S7(Reset),
+    S8(Reset),
+    S9(Reset),
+    S10(Reset),
+    S11(Reset),
+    S12(Reset),
+    S13(Reset),
+    S14(Reset),
+    S15(Reset),
+    S16(Reset),
+    S17(Reset),
+    S18(Reset),
+    S19(Reset),
+    S20(Reset),
+    S21(Reset),
+    S22(Reset),
+    S23(Reset),
+    S24(Reset),
+    S25(Reset),
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use serenity_commands::macros::{Command, Group};
+
+/// Reset.
+#[derive(Command)]
+#[command(name = "reset")]
+struct Reset;
+
+/// Prefix.
+#[derive(Group)]
+#[group(name = "prefix", hidden)]
+enum Prefix {
+    Reset(Reset),
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use serenity_commands::macros::Modal;
+
+/// Feedback
+#[derive(Modal)]
+#[modal(id = "feedback")]
+struct Feedback {
+    /// Subject
+    #[input(short, paragraph)]
+    subject: String,
+}
+
+fn main() {}
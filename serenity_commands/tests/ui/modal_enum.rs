@@ -0,0 +1,10 @@
+use serenity_commands::macros::Modal;
+
+/// Feedback
+#[derive(Modal)]
+#[modal(id = "feedback")]
+enum Feedback {
+    Subject(String),
+}
+
+fn main() {}
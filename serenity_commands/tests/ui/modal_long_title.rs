@@ -0,0 +1,11 @@
+use serenity_commands::macros::Modal;
+
+/// This title is far too long to fit in a modal window
+#[derive(Modal)]
+#[modal(id = "feedback")]
+struct Feedback {
+    /// Subject
+    subject: String,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use serenity_commands::macros::Modal;
+
+/// Feedback
+#[derive(Modal)]
+#[modal(id = "feedback")]
+struct Feedback {
+    /// Subject
+    #[input(min_len = 10, max_len = 5)]
+    subject: String,
+}
+
+fn main() {}
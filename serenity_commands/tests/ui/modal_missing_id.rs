@@ -0,0 +1,10 @@
+use serenity_commands::macros::Modal;
+
+/// Feedback
+#[derive(Modal)]
+struct Feedback {
+    /// Subject
+    subject: String,
+}
+
+fn main() {}
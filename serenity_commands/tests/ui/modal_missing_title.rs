@@ -0,0 +1,10 @@
+use serenity_commands::macros::Modal;
+
+#[derive(Modal)]
+#[modal(id = "feedback")]
+struct Feedback {
+    /// Subject
+    subject: String,
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use serenity_commands::macros::Modal;
+
+/// Feedback
+#[derive(Modal)]
+#[modal(id = "feedback")]
+struct Feedback {
+    /// A
+    a: String,
+    /// B
+    b: String,
+    /// C
+    c: String,
+    /// D
+    d: String,
+    /// E
+    e: String,
+    /// F
+    f: String,
+}
+
+fn main() {}
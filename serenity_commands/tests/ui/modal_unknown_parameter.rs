@@ -0,0 +1,11 @@
+use serenity_commands::macros::Modal;
+
+/// Feedback
+#[derive(Modal)]
+#[modal(id = "feedback", title = "Feedback")]
+struct Feedback {
+    /// Subject
+    subject: String,
+}
+
+fn main() {}
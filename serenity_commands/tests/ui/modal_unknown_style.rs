@@ -0,0 +1,12 @@
+use serenity_commands::macros::Modal;
+
+/// Feedback
+#[derive(Modal)]
+#[modal(id = "feedback")]
+struct Feedback {
+    /// Subject
+    #[input(long)]
+    subject: String,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use serenity_commands::macros::Command;
+
+/// Ping.
+#[derive(Command)]
+#[command(name = "ping")]
+struct Ping {
+    /// Amount of pings.
+    #[doc = "Amount of pongs."]
+    #[option(integer)]
+    n: i64,
+}
+
+fn main() {}
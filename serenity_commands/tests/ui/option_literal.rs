@@ -0,0 +1,12 @@
+use serenity_commands::macros::Command;
+
+/// Ping.
+#[derive(Command)]
+#[command(name = "ping")]
+struct Ping {
+    /// Amount of pings.
+    #[option("integer")]
+    n: i64,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use serenity_commands::macros::Command;
+
+/// Ping.
+#[derive(Command)]
+#[command(name = "ping")]
+struct Ping {
+    #[option(integer)]
+    n: i64,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use serenity_commands::macros::Options;
+
+#[derive(Options)]
+enum Common {
+    Reason(String),
+}
+
+fn main() {}
@@ -0,0 +1,76 @@
+use serenity_commands::macros::{Command, Options};
+
+#[derive(Options)]
+struct Common {
+    /// Option 0.
+    #[option(string)]
+    option_0: Option<String>,
+    /// Option 1.
+    #[option(string)]
+    option_1: Option<String>,
+    /// Option 2.
+    #[option(string)]
+    option_2: Option<String>,
+    /// Option 3.
+    #[option(string)]
+    option_3: Option<String>,
+    /// Option 4.
+    #[option(string)]
+    option_4: Option<String>,
+    /// Option 5.
+    #[option(string)]
+    option_5: Option<String>,
+    /// Option 6.
+    #[option(string)]
+    option_6: Option<String>,
+    /// Option 7.
+    #[option(string)]
+    option_7: Option<String>,
+    /// Option 8.
+    #[option(string)]
+    option_8: Option<String>,
+    /// Option 9.
+    #[option(string)]
+    option_9: Option<String>,
+    /// Option 10.
+    #[option(string)]
+    option_10: Option<String>,
+    /// Option 11.
+    #[option(string)]
+    option_11: Option<String>,
+    /// Option 12.
+    #[option(string)]
+    option_12: Option<String>,
+    /// Option 13.
+    #[option(string)]
+    option_13: Option<String>,
+    /// Option 14.
+    #[option(string)]
+    option_14: Option<String>,
+    /// Option 15.
+    #[option(string)]
+    option_15: Option<String>,
+    /// Option 16.
+    #[option(string)]
+    option_16: Option<String>,
+    /// Option 17.
+    #[option(string)]
+    option_17: Option<String>,
+    /// Option 18.
+    #[option(string)]
+    option_18: Option<String>,
+    /// Option 19.
+    #[option(string)]
+    option_19: Option<String>,
+}
+
+/// Ban a user.
+#[derive(Command)]
+struct Ban {
+    #[option(flatten)]
+    common: Common,
+    #[option(flatten)]
+    more: Common,
+}
+
+fn main() {}
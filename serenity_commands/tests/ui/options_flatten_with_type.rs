@@ -0,0 +1,17 @@
+use serenity_commands::macros::{Command, Options};
+
+#[derive(Options)]
+struct Common {
+    /// The reason.
+    #[option(string)]
+    reason: String,
+}
+
+/// Ban a user.
+#[derive(Command)]
+struct Ban {
+    #[option(flatten, string)]
+    common: Common,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use serenity_commands::macros::Command;
+
+/// Reset.
+#[derive(Command)]
+#[command(name = "reset")]
+struct Reset;
+
+/// Configure.
+#[derive(Command)]
+#[command(name = "config")]
+enum Config {
+    #[option(subcommand, group)]
+    Reset(Reset),
+}
+
+fn main() {}
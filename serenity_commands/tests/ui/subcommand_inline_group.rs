@@ -0,0 +1,12 @@
+use serenity_commands::macros::Command;
+
+/// Configure.
+#[derive(Command)]
+#[command(name = "config")]
+enum Config {
+    /// Reset.
+    #[option(group)]
+    Reset,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use serenity_commands::macros::Command;
+
+/// Configure.
+#[derive(Command)]
+#[command(name = "config")]
+enum Config {
+    #[option(subcommand)]
+    Reset,
+}
+
+fn main() {}
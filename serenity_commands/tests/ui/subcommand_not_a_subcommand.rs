@@ -0,0 +1,10 @@
+use serenity_commands::macros::Command;
+
+/// Configure.
+#[derive(Command)]
+#[command(name = "config")]
+enum Config {
+    Reset(String),
+}
+
+fn main() {}
@@ -0,0 +1,262 @@
+//! The paths and builders of the library targeted by the generated code,
+//! either a version of Serenity or Twilight, which is selected by the
+//! features of the crate.
+//!
+//! Commands are registered by converting their `CommandDefinition` into the
+//! builders of the library. Serenity 0.10 and 0.11 fill builders in closures,
+//! while Serenity 0.12 has builders that are values, so functions registering
+//! commands take and return a builder with the former and only return one
+//! with the latter. Twilight has no builders, but its commands are created as
+//! values in the same way.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Error, Ident, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Serenity0_10,
+    Serenity0_11,
+    Serenity0_12,
+    Twilight,
+}
+
+impl Backend {
+    /// Returns the oldest version of Serenity enabled, or Twilight if none
+    /// is, or Serenity 0.10 if nothing is.
+    pub fn get() -> Self {
+        if cfg!(feature = "serenity_0_10") {
+            Self::Serenity0_10
+        } else if cfg!(feature = "serenity_0_11") {
+            Self::Serenity0_11
+        } else if cfg!(feature = "serenity_0_12") {
+            Self::Serenity0_12
+        } else if cfg!(feature = "twilight") {
+            Self::Twilight
+        } else {
+            Self::Serenity0_10
+        }
+    }
+}
+
+/// Whether builders are values rather than being filled in closures.
+pub fn value_builders() -> bool {
+    matches!(Backend::get(), Backend::Serenity0_12 | Backend::Twilight)
+}
+
+fn is_twilight() -> bool {
+    Backend::get() == Backend::Twilight
+}
+
+/// Fails on Twilight, which only supports application commands, with an
+/// error saying that `what` is not supported.
+pub fn require_serenity(span: Span, what: &str) -> Result<()> {
+    if is_twilight() {
+        Err(Error::new(span, format!("{} is not supported with Twilight", what)))
+    } else {
+        Ok(())
+    }
+}
+
+fn select(closure: TokenStream, value: TokenStream) -> TokenStream {
+    if value_builders() {
+        value
+    } else {
+        closure
+    }
+}
+
+fn select_path(closure: TokenStream, value: TokenStream, twilight: TokenStream) -> TokenStream {
+    if is_twilight() {
+        twilight
+    } else {
+        select(closure, value)
+    }
+}
+
+/// The permissions of the library, whose constants are named after Discord's.
+pub fn permissions() -> TokenStream {
+    select_path(
+        quote!(serenity_commands::serenity::model::Permissions),
+        quote!(serenity_commands::serenity::model::Permissions),
+        quote!(serenity_commands::twilight_model::guild::Permissions),
+    )
+}
+
+pub fn interaction() -> TokenStream {
+    select_path(
+        quote!(serenity_commands::serenity::model::interactions::application_command::ApplicationCommandInteraction),
+        quote!(serenity_commands::serenity::model::application::CommandInteraction),
+        quote!(serenity_commands::twilight_model::application::interaction::application_command::CommandData),
+    )
+}
+
+pub fn command_data() -> TokenStream {
+    select_path(
+        quote!(serenity_commands::serenity::model::interactions::application_command::ApplicationCommandInteractionData),
+        quote!(serenity_commands::serenity::model::application::CommandData),
+        quote!(serenity_commands::twilight_model::application::interaction::application_command::CommandData),
+    )
+}
+
+/// The type of the commands returned by Discord once registered.
+pub fn registered_command() -> TokenStream {
+    select(
+        quote!(serenity_commands::serenity::model::interactions::application_command::ApplicationCommand),
+        quote!(serenity_commands::serenity::model::application::Command),
+    )
+}
+
+pub fn component_interaction() -> TokenStream {
+    select(
+        quote!(serenity_commands::serenity::model::interactions::message_component::MessageComponentInteraction),
+        quote!(serenity_commands::serenity::model::application::ComponentInteraction),
+    )
+}
+
+pub fn component_data() -> TokenStream {
+    select(
+        quote!(serenity_commands::serenity::model::interactions::message_component::MessageComponentInteractionData),
+        quote!(serenity_commands::serenity::model::application::ComponentInteractionData),
+    )
+}
+
+pub fn button_style() -> TokenStream {
+    select(
+        quote!(serenity_commands::serenity::model::interactions::message_component::ButtonStyle),
+        quote!(serenity_commands::serenity::model::application::ButtonStyle),
+    )
+}
+
+pub fn command_builder() -> TokenStream {
+    select_path(
+        quote!(serenity_commands::serenity::builder::CreateApplicationCommand),
+        quote!(serenity_commands::serenity::builder::CreateCommand),
+        quote!(serenity_commands::twilight_model::application::command::Command),
+    )
+}
+
+pub fn option_builder() -> TokenStream {
+    select_path(
+        quote!(serenity_commands::serenity::builder::CreateApplicationCommandOption),
+        quote!(serenity_commands::serenity::builder::CreateCommandOption),
+        quote!(serenity_commands::twilight_model::application::command::CommandOption),
+    )
+}
+
+/// Generates the data of the command in `interaction`, which is the data
+/// itself with Twilight.
+pub fn interaction_data(interaction: &Ident) -> TokenStream {
+    if is_twilight() {
+        quote!(#interaction)
+    } else {
+        quote!(#interaction.data)
+    }
+}
+
+/// Generates the deserialization of the value taken by `parse` out of the
+/// payload of an interaction, `value`, as a `Result` with a `ParseError`.
+pub fn deserialize_interaction(value: &Ident) -> TokenStream {
+    if is_twilight() {
+        quote!(serenity_commands::twilight::command_data(#value))
+    } else {
+        let interaction = interaction();
+
+        quote! {
+            {
+                use serenity_commands::serde::Deserialize;
+
+                #interaction::deserialize(#value)
+                    .map_err(|e| serenity_commands::error::ParseError::InvalidPayload(e.to_string()))
+            }
+        }
+    }
+}
+
+/// Returns the name of a function registering a command or an option, such
+/// as `register_command` or `create_command` for `command`.
+pub fn builder_fn(kind: &str) -> Ident {
+    if value_builders() {
+        format_ident!("create_{}", kind)
+    } else {
+        format_ident!("register_{}", kind)
+    }
+}
+
+/// Generates a function registering a command out of `definition`, a
+/// `CommandDefinition`.
+pub fn command_fn(vis: TokenStream, ident: &Ident, definition: TokenStream) -> TokenStream {
+    let builder = command_builder();
+
+    if value_builders() {
+        quote! {
+            #vis fn #ident() -> #builder {
+                #definition.into()
+            }
+        }
+    } else {
+        quote! {
+            #vis fn #ident(cmd: &mut #builder) -> &mut #builder {
+                #definition.register(cmd)
+            }
+        }
+    }
+}
+
+/// Generates a function registering an option out of `definition`, an
+/// `OptionDefinition`.
+pub fn option_fn(vis: TokenStream, ident: &Ident, definition: TokenStream) -> TokenStream {
+    let builder = option_builder();
+
+    if value_builders() {
+        quote! {
+            #vis fn #ident() -> #builder {
+                #definition.into()
+            }
+        }
+    } else {
+        quote! {
+            #vis fn #ident(opt: &mut #builder) -> &mut #builder {
+                #definition.register(opt)
+            }
+        }
+    }
+}
+
+/// Generates a trait function registering a command, delegating to the
+/// inherent one.
+pub fn delegate_command_fn(ident: &Ident, inherent: &Ident) -> TokenStream {
+    let builder = command_builder();
+
+    select(
+        quote! {
+            fn #ident(cmd: &mut #builder) -> &mut #builder {
+                Self::#inherent(cmd)
+            }
+        },
+        quote! {
+            fn #ident() -> #builder {
+                Self::#inherent()
+            }
+        },
+    )
+}
+
+/// Generates a trait function registering an option, delegating to the
+/// inherent one.
+pub fn delegate_option_fn(ident: &Ident, inherent: &Ident) -> TokenStream {
+    let builder = option_builder();
+
+    select(
+        quote! {
+            fn #ident(opt: &mut #builder) -> &mut #builder {
+                Self::#inherent(opt)
+            }
+        },
+        quote! {
+            fn #ident() -> #builder {
+                Self::#inherent()
+            }
+        },
+    )
+}
@@ -6,26 +6,157 @@ mod parse;
 
 use parse::*;
 
+use crate::backend;
+use crate::common::{add_trait_bound, is_generic};
+
 pub fn derive_command(item: TokenStream) -> Result<TokenStream> {
     let input = parse2::<DeriveInput>(item)?;
 
     let Command {
         name: cmd,
         description,
+        prefix,
+        default_member_permissions,
+        dm_permission,
         data,
     } = Command::new(&input)?;
 
     let name = input.ident;
 
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut generics = input.generics;
+
+    match &data {
+        CommandData::SubCommands(subcommands) => {
+            for sub in subcommands {
+                match sub {
+                    SubCommandVariant::Type(sub) => {
+                        add_trait_bound(&mut generics, &sub.ty, sub.to_trait());
+
+                        if prefix {
+                            add_trait_bound(
+                                &mut generics,
+                                &sub.ty,
+                                quote!(serenity_commands::message::FromArguments),
+                            );
+                        }
+                    },
+                    SubCommandVariant::Inline(sub) => {
+                        for f in flattened(sub.fields()) {
+                            add_trait_bound(
+                                &mut generics,
+                                &f.ty,
+                                quote!(serenity_commands::Options),
+                            );
+                        }
+                    },
+                }
+            }
+        },
+        CommandData::Options(fields) => {
+            for f in flattened(fields) {
+                add_trait_bound(&mut generics, &f.ty, quote!(serenity_commands::Options));
+            }
+        },
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let mut extra = match &data {
+        CommandData::SubCommands(subcommands) => generate_subcommand_container_fns(subcommands),
+        CommandData::Options(options) => generate_command_fns(options),
+    };
+
+    if prefix {
+        extra.extend(generate_message_fns(&data));
+    }
+
+    let interaction_option = quote!(serenity_commands::model::InteractionOption);
+
+    let subcommand_impl = match &data {
+        CommandData::SubCommands(_) => TokenStream::new(),
+        CommandData::Options(_) => {
+            let register_subcommand = backend::builder_fn("subcommand");
+            let register_option = backend::builder_fn("option");
+
+            let subcommand_fn =
+                backend::delegate_option_fn(&register_subcommand, &register_subcommand);
+            let option_fn = backend::delegate_option_fn(&register_option, &register_subcommand);
+
+            quote! {
+                impl #impl_generics serenity_commands::SubCommand for #name #ty_generics #where_clause {
+                    const METADATA: &'static serenity_commands::info::CommandMeta = Self::METADATA;
+
+                    fn name() -> &'static str {
+                        Self::name()
+                    }
+
+                    #subcommand_fn
+
+                    fn parse_subcommand(
+                        option: #interaction_option
+                    ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+                        Self::parse_subcommand(option)
+                    }
+
+                    fn to_subcommand_option(&self) -> #interaction_option {
+                        self.to_subcommand_option()
+                    }
+                }
+
+                impl #impl_generics serenity_commands::SubCommandOption for #name #ty_generics #where_clause {
+                    const METADATA: &'static serenity_commands::info::CommandMeta = Self::METADATA;
+
+                    fn name() -> &'static str {
+                        Self::name()
+                    }
+
+                    #option_fn
+
+                    fn parse_option(
+                        option: #interaction_option
+                    ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+                        Self::parse_subcommand(option)
+                    }
+
+                    fn to_option(&self) -> #interaction_option {
+                        self.to_subcommand_option()
+                    }
+                }
+            }
+        },
+    };
 
-    let extra = match data {
-        CommandData::SubCommands(subcommands) => generate_subcommand_container_fns(&subcommands),
-        CommandData::Options(options) => generate_command_fns(&options),
+    let message_impl = if prefix {
+        quote! {
+            impl #impl_generics serenity_commands::message::FromArguments for #name #ty_generics #where_clause {
+                fn parse_arguments(
+                    args: &mut serenity_commands::message::Arguments
+                ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+                    Self::parse_arguments(args)
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
     };
 
+    let (check_consts, checks) = generate_option_count_checks(&data, &generics);
+    let permissions = generate_permissions(default_member_permissions.as_deref(), dm_permission);
+    let metadata = generate_metadata(&cmd, &description, &data, permissions);
+
+    let command_data = backend::command_data();
+    let register_command = backend::builder_fn("command");
+    let command_fn = backend::delegate_command_fn(&register_command, &register_command);
+
     let output = quote! {
         impl #impl_generics #name #ty_generics #where_clause {
+            #check_consts
+
+            pub(crate) const METADATA: &'static serenity_commands::info::CommandMeta = {
+                #checks
+                #metadata
+            };
+
             pub(crate) fn name() -> &'static str {
                 #cmd
             }
@@ -34,191 +165,509 @@ pub fn derive_command(item: TokenStream) -> Result<TokenStream> {
                 #description
             }
 
+            pub(crate) fn definition() -> serenity_commands::model::CommandDefinition {
+                serenity_commands::model::CommandDefinition::from(Self::METADATA)
+            }
+
             #extra
         }
+
+        impl #impl_generics serenity_commands::Command for #name #ty_generics #where_clause {
+            const METADATA: &'static serenity_commands::info::CommandMeta = Self::METADATA;
+
+            fn name() -> &'static str {
+                Self::name()
+            }
+
+            #command_fn
+
+            fn parse_command(
+                data: #command_data
+            ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+                Self::parse_command(data)
+            }
+
+            fn definition() -> serenity_commands::model::CommandDefinition {
+                Self::definition()
+            }
+        }
+
+        #subcommand_impl
+
+        #message_impl
     };
 
     Ok(output)
 }
 
-fn generate_option_registration_fn(opt: &CommandOption, tokens: &mut TokenStream) -> Ident {
-    let CommandOption {
-        ident,
-        ty: _,
-        required,
-        name,
-        description,
-        kind,
-    } = opt;
+fn generate_subcommand_container_fns(subcommands: &[SubCommandVariant]) -> TokenStream {
+    let mut parsing = TokenStream::new();
+    let mut conversions = TokenStream::new();
+
+    for sub in subcommands {
+        match sub {
+            SubCommandVariant::Type(sub) => {
+                let ident = &sub.ident;
+                let ty = &sub.ty;
+                let tr = sub.to_trait();
+
+                let parsing_fn = sub.to_parsing_fn();
+                let option_fn = sub.to_option_fn();
+
+                parsing.extend(quote! {
+                    if opt.name == <#ty as #tr>::name() {
+                        return Ok(Self::#ident(<#ty as #tr>::#parsing_fn(opt)?));
+                    }
+                });
+
+                conversions.extend(quote! {
+                    Self::#ident(v) => vec![<#ty as #tr>::#option_fn(v)],
+                });
+            },
+            SubCommandVariant::Inline(sub) => {
+                let name = &sub.name;
+
+                let fields = sub.fields();
+                let pattern = sub.pattern();
+
+                let option_parsing = generate_option_parsing(
+                    fields,
+                    quote!(&mut options),
+                    quote!(options),
+                    quote!(s => return Err(serenity_commands::error::ParseError::UnknownOption(s.to_string())),),
+                );
+
+                parsing.extend(quote! {
+                    if opt.name == #name {
+                        #[allow(unused_mut)]
+                        let mut options = serenity_commands::options::sub_options(opt);
+
+                        #option_parsing
+
+                        return Ok(#pattern);
+                    }
+                });
+
+                let option_conversions = generate_option_conversions(fields, |f| quote!(#f));
+
+                conversions.extend(quote! {
+                    #pattern => vec![serenity_commands::options::subcommand_option(#name, { #option_conversions })],
+                });
+            },
+        }
+    }
 
-    let fn_name = format_ident!("register_option_{}", ident);
+    let interaction_option = quote!(serenity_commands::model::InteractionOption);
+    let command_data = backend::command_data();
 
-    let extra = {
-        let mut extra = TokenStream::new();
+    let command_registration = backend::command_fn(
+        quote!(pub(crate)),
+        &backend::builder_fn("command"),
+        quote!(Self::definition()),
+    );
 
-        if *required {
-            extra.extend(quote! {
-                opt.required(true);
-            });
-        }
+    quote! {
+        #command_registration
 
-        extra
-    };
+        pub(crate) fn parse(
+            options: Vec<#interaction_option>
+        ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+            for opt in options {
+                #parsing
 
-    tokens.extend(quote! {
-        fn #fn_name(
-            opt: &mut serenity_commands::serenity::builder::CreateApplicationCommandOption
-        ) -> &mut serenity_commands::serenity::builder::CreateApplicationCommandOption {
-            use serenity_commands::serenity::model::interactions::application_command::ApplicationCommandOptionType;
+                return Err(serenity_commands::error::ParseError::UnknownSubCommand(opt.name.clone()));
+            }
 
-            opt.name(#name).description(#description).kind(ApplicationCommandOptionType::#kind);
+            Err(serenity_commands::error::ParseError::MissingName)
+        }
 
-            #extra
+        pub(crate) fn parse_command(
+            data: #command_data
+        ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+            if data.name != Self::name() {
+                return Err(serenity_commands::error::ParseError::UnknownCommand(data.name.clone()));
+            }
 
-            opt
+            let options = <serenity_commands::model::InteractionOptions as ::core::convert::TryFrom<_>>::try_from(&data)?;
+
+            Self::parse(options.options)
         }
-    });
 
-    fn_name
+        pub(crate) fn to_options(&self) -> Vec<#interaction_option> {
+            match self {
+                #conversions
+            }
+        }
+    }
 }
 
-fn generate_subcommand_container_fns(subcommands: &[SubCommand]) -> TokenStream {
-    let subcommand_idents = subcommands.iter().map(|o| &o.ident).collect::<Vec<_>>();
+fn generate_command_fns(fields: &[CommandField]) -> TokenStream {
+    let parsing = generate_option_parsing(
+        fields,
+        quote!(&mut options),
+        quote!(options),
+        quote!(s => return Err(serenity_commands::error::ParseError::UnknownOption(s.to_string())),),
+    );
+
+    let field_idents = fields.iter().map(CommandField::ident);
+    let option_conversions = generate_option_conversions(fields, |f| quote!(&self.#f));
 
-    let subcommand_vars = subcommands
-        .iter()
-        .map(|o| format_ident!("{}", o.ident.to_string().to_lowercase()))
-        .collect::<Vec<_>>();
+    let command_registration = backend::command_fn(
+        quote!(pub(crate)),
+        &backend::builder_fn("command"),
+        quote!(Self::definition()),
+    );
 
-    let subcommand_registration_fns = subcommands.iter().map(|o| o.kind.to_registration_fn());
+    let subcommand_registration = backend::option_fn(
+        quote!(pub(crate)),
+        &backend::builder_fn("subcommand"),
+        quote!(serenity_commands::model::OptionDefinition::from(Self::METADATA)),
+    );
 
-    let subcommand_parsing_fns = subcommands.iter().map(|o| o.kind.to_parsing_fn());
+    let interaction_option = quote!(serenity_commands::model::InteractionOption);
+    let command_data = backend::command_data();
 
     quote! {
-        pub(crate) fn register_command(
-            cmd: &mut serenity_commands::serenity::builder::CreateApplicationCommand
-        ) -> &mut serenity_commands::serenity::builder::CreateApplicationCommand {
-            cmd.name(Self::name())
-                .description(Self::description())
-                #(.create_subcommand(#subcommand_idents::#subcommand_registration_fns))*
+        #command_registration
+
+        #subcommand_registration
+
+        #[allow(unused_mut)]
+        pub(crate) fn parse(
+            mut options: Vec<#interaction_option>
+        ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+            #parsing
+
+            Ok(Self { #(#field_idents),* })
         }
 
         pub(crate) fn parse_command(
-            data: serenity_commands::serenity::model::interactions::application_command::ApplicationCommandInteractionData
+            data: #command_data
         ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
             if data.name != Self::name() {
                 return Err(serenity_commands::error::ParseError::UnknownCommand(data.name.clone()));
             }
 
-            #(let #subcommand_vars = #subcommand_idents::name();)*
+            let options = <serenity_commands::model::InteractionOptions as ::core::convert::TryFrom<_>>::try_from(&data)?;
 
-            for opt in data.subcommands {
-                #(if opt.name == #subcommand_vars {
-                    return Ok(Self::#subcommand_idents(#subcommand_idents::#subcommand_parsing_fns(opt)?));
-                })*
+            Self::parse(options.options)
+        }
 
-                return Err(serenity_commands::error::ParseError::UnknownSubCommand(opt.name.clone()));
+        pub(crate) fn parse_subcommand(
+            option: #interaction_option
+        ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+            if option.name != Self::name() {
+                return Err(serenity_commands::error::ParseError::UnknownSubCommand(option.name.clone()));
             }
 
-            unreachable!()
+            Self::parse(serenity_commands::options::sub_options(option))
+        }
+
+        pub(crate) fn to_options(&self) -> Vec<#interaction_option> {
+            #option_conversions
+        }
+
+        pub(crate) fn to_subcommand_option(&self) -> #interaction_option {
+            serenity_commands::options::subcommand_option(Self::name(), self.to_options())
         }
     }
 }
 
-fn generate_command_fns(options: &[CommandOption]) -> TokenStream {
-    let mut option_fns = TokenStream::new();
+pub fn derive_options(item: TokenStream) -> Result<TokenStream> {
+    let input = parse2::<DeriveInput>(item)?;
+
+    let fields = parse_option_set(&input)?;
 
-    let mut option_fn_names = Vec::new();
+    let name = input.ident;
+
+    let mut generics = input.generics;
 
-    for opt in options {
-        option_fn_names.push(generate_option_registration_fn(opt, &mut option_fns));
+    for f in flattened(&fields) {
+        add_trait_bound(&mut generics, &f.ty, quote!(serenity_commands::Options));
     }
 
-    let option_idents = options.iter().map(|o| &o.ident).collect::<Vec<_>>();
-    let option_names = options.iter().map(|o| &o.name);
-    let option_kinds = options.iter().map(|o| o.kind);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let option_data_extractions = options.iter().map(|o| o.kind.to_data_option_value_extraction());
-    let option_requirement_cases = generate_requirement_cases(options);
+    let parsing = generate_option_parsing(
+        &fields,
+        quote!(options),
+        quote!(std::mem::take(options)),
+        quote!(_ => rest.push(opt),),
+    );
 
-    quote! {
-        #option_fns
+    let field_idents = fields.iter().map(CommandField::ident).collect::<Vec<_>>();
+    let option_conversions = generate_option_conversions(&fields, |f| quote!(&self.#f));
+    let argument_parsing = generate_argument_parsing(&fields);
+    let option_count = generate_option_count(&fields);
+    let option_metadata = generate_option_metadata(&fields);
+
+    let interaction_option = quote!(serenity_commands::model::InteractionOption);
+    let option_builder = backend::option_builder();
+
+    let output = quote! {
+        impl #impl_generics serenity_commands::Options for #name #ty_generics #where_clause {
+            const COUNT: usize = #option_count;
+
+            const METADATA: &'static [serenity_commands::info::OptionEntry] = #option_metadata;
+
+            fn create_options() -> Vec<#option_builder> {
+                serenity_commands::model::OptionDefinition::from_entries(Self::METADATA)
+                    .into_iter()
+                    .map(Into::into)
+                    .collect()
+            }
+
+            fn parse_options(
+                options: &mut Vec<#interaction_option>
+            ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+                let mut rest = Vec::new();
+
+                #parsing
+
+                *options = rest;
+
+                Ok(Self { #(#field_idents),* })
+            }
+
+            fn to_options(&self) -> Vec<#interaction_option> {
+                #option_conversions
+            }
 
-        pub(crate) fn register_command(
-            cmd: &mut serenity_commands::serenity::builder::CreateApplicationCommand
-        ) -> &mut serenity_commands::serenity::builder::CreateApplicationCommand {
-            cmd.name(Self::name())
-                .description(Self::description())
-                #(.create_option(Self::#option_fn_names))*
+            #[allow(unused_variables)]
+            fn parse_arguments(
+                args: &mut serenity_commands::message::Arguments
+            ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+                #argument_parsing
+
+                Ok(Self { #(#field_idents),* })
+            }
         }
+    };
+
+    Ok(output)
+}
+
+/// Generates the parsing of the options in `source`, binding each field to
+/// a variable. Flattened options are first taken out of `options`, and
+/// options unknown to the command are handled by `fallback`.
+fn generate_option_parsing(
+    fields: &[CommandField],
+    options_ref: TokenStream,
+    source: TokenStream,
+    fallback: TokenStream,
+) -> TokenStream {
+    let flattened_idents = flattened(fields).map(|f| &f.ident);
+    let flattened_types = flattened(fields).map(|f| &f.ty);
+
+    let option_idents = options(fields).map(|o| &o.ident).collect::<Vec<_>>();
+    let option_names = options(fields).map(|o| &o.name);
+
+    let option_requirement_cases = generate_requirement_cases(fields);
+
+    let option_extractions = options(fields).map(|o| {
+        let ident = &o.ident;
+        let parse_fn = o.kind.to_parse_fn();
+
+        quote!(#ident = serenity_commands::options::#parse_fn(opt)?;)
+    });
+
+    quote! {
+        #(let #flattened_idents = <#flattened_types as serenity_commands::Options>::parse_options(#options_ref)?;)*
 
-        pub(crate) fn register_subcommand(
-            opt: &mut serenity_commands::serenity::builder::CreateApplicationCommandOption
-        ) -> &mut serenity_commands::serenity::builder::CreateApplicationCommandOption {
-            use serenity_commands::serenity::model::interactions::application_command::ApplicationCommandOptionType;
+        #(let mut #option_idents = None;)*
 
-            opt.name(Self::name())
-                .description(Self::description())
-                .kind(ApplicationCommandOptionType::SubCommand)
-                #(.create_sub_option(Self::#option_fn_names))*
+        for opt in #source {
+            match &opt.name[..] {
+                #(#option_names => {
+                    #option_extractions
+                }),*
+                #fallback
+            }
         }
 
-        pub(crate) fn parse(
-            options: Vec<serenity_commands::serenity::model::interactions::application_command::ApplicationCommandInteractionDataOption>
-        ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
-            use serenity_commands::serenity::model::interactions::application_command::{ApplicationCommandOptionType, ApplicationCommandInteractionDataOptionValue};
+        #option_requirement_cases
+    }
+}
 
-            #(let mut #option_idents = None;)*
+/// Generates the number of options, including flattened ones.
+fn generate_option_count(fields: &[CommandField]) -> TokenStream {
+    let count = options(fields).count();
+    let flattened_types = flattened(fields).map(|f| &f.ty);
 
-            for opt in options {
-                match &opt.name[..] {
-                    #(#option_names => {
-                        if let Some(v) = opt.resolved {
-                            match v {
-                                ApplicationCommandInteractionDataOptionValue::#option_data_extractions => #option_idents = Some(v),
-                                _ => {
-                                    return Err(serenity_commands::error::ParseError::InvalidType(
-                                        ApplicationCommandOptionType::#option_kinds
-                                    ));
-                                },
-                            };
+    quote!(#count #(+ <#flattened_types as serenity_commands::Options>::COUNT)*)
+}
+
+/// Generates the checks of the number of options of a command and of its
+/// inline subcommands, returning associated constants and statements to be
+/// placed in the metadata, which registration is created from.
+fn generate_option_count_checks(
+    data: &CommandData,
+    generics: &Generics,
+) -> (TokenStream, TokenStream) {
+    let checks = match data {
+        CommandData::Options(fields) => {
+            vec![generate_option_count_check(
+                fields,
+                generics,
+                &format_ident!("OPTION_COUNT_CHECK"),
+            )]
+        },
+        CommandData::SubCommands(subcommands) => subcommands
+            .iter()
+            .filter_map(|sub| match sub {
+                SubCommandVariant::Inline(sub) => Some(generate_option_count_check(
+                    sub.fields(),
+                    generics,
+                    &format_ident!("OPTION_COUNT_CHECK_{}", sub.ident.to_string().to_uppercase()),
+                )),
+                SubCommandVariant::Type(_) => None,
+            })
+            .collect(),
+    };
+
+    checks.into_iter().unzip()
+}
+
+/// Generates a check that the options, once flattened, do not exceed the
+/// limit of Discord, returning an associated constant and a statement.
+///
+/// Constants in blocks cannot refer to type parameters, so the check is done
+/// through an associated constant when flattened types are generic.
+fn generate_option_count_check(
+    fields: &[CommandField],
+    generics: &Generics,
+    check: &Ident,
+) -> (TokenStream, TokenStream) {
+    if flattened(fields).next().is_none() {
+        return (TokenStream::new(), TokenStream::new());
+    }
+
+    let count = generate_option_count(fields);
+
+    let assertion = quote! {
+        {
+            let count: usize = #count;
+            assert!(count <= 25, "a command cannot have more than 25 options");
+        }
+    };
+
+    if flattened(fields).any(|f| is_generic(&f.ty, generics)) {
+        (quote!(const #check: () = #assertion;), quote!(let () = Self::#check;))
+    } else {
+        (TokenStream::new(), quote!(const _: () = #assertion;))
+    }
+}
+
+fn generate_message_fns(data: &CommandData) -> TokenStream {
+    let parsing = match data {
+        CommandData::SubCommands(subcommands) => {
+            let subcommand_parsing = subcommands.iter().map(|sub| match sub {
+                SubCommandVariant::Type(sub) => {
+                    let ident = &sub.ident;
+                    let ty = &sub.ty;
+                    let tr = sub.to_trait();
+
+                    quote! {
+                        if name == <#ty as #tr>::name() {
+                            return Ok(Self::#ident(
+                                <#ty as serenity_commands::message::FromArguments>::parse_arguments(args)?
+                            ));
                         }
-                    }),*
-                    s => return Err(serenity_commands::error::ParseError::UnknownOption(s.to_string())),
-                }
+                    }
+                },
+                SubCommandVariant::Inline(sub) => {
+                    let name = &sub.name;
+                    let pattern = sub.pattern();
+                    let argument_parsing = generate_argument_parsing(sub.fields());
+
+                    quote! {
+                        if name == #name {
+                            #argument_parsing
+
+                            return Ok(#pattern);
+                        }
+                    }
+                },
+            });
+
+            quote! {
+                let name = args.name()?;
+
+                #(#subcommand_parsing)*
+
+                Err(serenity_commands::error::ParseError::UnknownSubCommand(name))
             }
+        },
+        CommandData::Options(fields) => {
+            let argument_parsing = generate_argument_parsing(fields);
+            let field_idents = fields.iter().map(CommandField::ident);
 
-            #option_requirement_cases
+            quote! {
+                #argument_parsing
 
-            Ok(Self { #(#option_idents),* })
-        }
+                Ok(Self { #(#field_idents),* })
+            }
+        },
+    };
 
-        pub(crate) fn parse_command(
-            data: serenity_commands::serenity::model::interactions::application_command::ApplicationCommandInteractionData
+    quote! {
+        pub(crate) fn parse_message(
+            content: &str
         ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
-            if data.name != Self::name() {
-                return Err(serenity_commands::error::ParseError::UnknownCommand(data.name.clone()));
+            let mut args = serenity_commands::message::Arguments::new(content)?;
+
+            let name = args.name()?;
+            if name != Self::name() {
+                return Err(serenity_commands::error::ParseError::UnknownCommand(name));
             }
 
-            Self::parse(data.options)
+            let cmd = Self::parse_arguments(&mut args)?;
+            args.finish()?;
+
+            Ok(cmd)
         }
 
-        pub(crate) fn parse_subcommand(
-            option: serenity_commands::serenity::model::interactions::application_command::ApplicationCommandInteractionDataOption
+        #[allow(unused_variables)]
+        pub(crate) fn parse_arguments(
+            args: &mut serenity_commands::message::Arguments
         ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
-            if option.name != Self::name() {
-                return Err(serenity_commands::error::ParseError::UnknownSubCommand(option.name.clone()));
-            }
-
-            Self::parse(option.options)
+            #parsing
         }
     }
 }
 
-fn generate_requirement_cases(options: &[CommandOption]) -> TokenStream {
+/// Generates the parsing of the arguments of a text command, binding each
+/// field to a variable.
+fn generate_argument_parsing(fields: &[CommandField]) -> TokenStream {
+    let mut res = TokenStream::new();
+
+    for field in fields {
+        res.extend(match field {
+            CommandField::Option(opt) => {
+                let ident = &opt.ident;
+                let name = &opt.name;
+                let argument_fn = opt.kind.to_argument_fn();
+
+                quote!(let #ident = args.#argument_fn(#name)?;)
+            },
+            CommandField::Flatten(f) => {
+                let ident = &f.ident;
+                let ty = &f.ty;
+
+                quote!(let #ident = <#ty as serenity_commands::Options>::parse_arguments(args)?;)
+            },
+        });
+    }
+
+    res.extend(generate_requirement_cases(fields));
+
+    res
+}
+
+fn generate_requirement_cases(fields: &[CommandField]) -> TokenStream {
     let mut res = TokenStream::new();
 
-    for opt in options {
+    for opt in options(fields) {
         let ident = &opt.ident;
         let name = &opt.name;
 
@@ -231,3 +680,178 @@ fn generate_requirement_cases(options: &[CommandOption]) -> TokenStream {
 
     res
 }
+
+/// Generates the conversion of the fields back into options, where `access`
+/// returns a reference to a field.
+fn generate_option_conversions(
+    fields: &[CommandField],
+    access: impl Fn(&Ident) -> TokenStream,
+) -> TokenStream {
+    let mut res = TokenStream::new();
+
+    for field in fields {
+        let opt = match field {
+            CommandField::Option(opt) => opt,
+            CommandField::Flatten(f) => {
+                let field = access(&f.ident);
+
+                res.extend(quote! {
+                    options.extend(serenity_commands::Options::to_options(#field));
+                });
+                continue;
+            },
+        };
+
+        let field = access(&opt.ident);
+        let name = &opt.name;
+        let option_fn = opt.kind.to_option_fn();
+
+        let push = quote! {
+            options.push(serenity_commands::options::#option_fn(#name, v));
+        };
+
+        res.extend(if opt.required {
+            quote! {
+                let v = #field;
+                #push
+            }
+        } else {
+            quote! {
+                if let Some(v) = #field {
+                    #push
+                }
+            }
+        });
+    }
+
+    quote! {
+        #[allow(unused_mut)]
+        let mut options = Vec::new();
+
+        #res
+
+        options
+    }
+}
+
+/// Generates the metadata of a command from its name, description, data and
+/// permission fields.
+fn generate_metadata(
+    name: &str,
+    description: &str,
+    data: &CommandData,
+    permissions: TokenStream,
+) -> TokenStream {
+    match data {
+        CommandData::Options(fields) => generate_command_metadata(
+            name,
+            description,
+            generate_option_metadata(fields),
+            quote!(&[]),
+            permissions,
+        ),
+        CommandData::SubCommands(subcommands) => {
+            let subcommands = subcommands.iter().map(|sub| match sub {
+                SubCommandVariant::Type(sub) => {
+                    let ty = &sub.ty;
+                    let tr = sub.to_trait();
+
+                    quote!(<#ty as #tr>::METADATA)
+                },
+                SubCommandVariant::Inline(sub) => generate_command_metadata(
+                    &sub.name,
+                    &sub.description,
+                    generate_option_metadata(sub.fields()),
+                    quote!(&[]),
+                    generate_permissions(None, None),
+                ),
+            });
+
+            generate_command_metadata(
+                name,
+                description,
+                quote!(&[]),
+                quote!(&[#(#subcommands),*]),
+                permissions,
+            )
+        },
+    }
+}
+
+fn generate_command_metadata(
+    name: &str,
+    description: &str,
+    options: TokenStream,
+    subcommands: TokenStream,
+    permissions: TokenStream,
+) -> TokenStream {
+    quote! {
+        &serenity_commands::info::CommandMeta {
+            name: #name,
+            description: #description,
+            options: #options,
+            subcommands: #subcommands,
+            #permissions
+        }
+    }
+}
+
+/// Generates the permission fields of the metadata of a command, taking the
+/// bits of each permission from the library, which rejects unknown ones.
+fn generate_permissions(names: Option<&[Ident]>, dm_permission: Option<bool>) -> TokenStream {
+    let default_member_permissions = match names {
+        Some(names) => {
+            let permissions = backend::permissions();
+            let strings = names.iter().map(|name| name.to_string());
+
+            quote! {
+                Some(serenity_commands::info::PermissionsMeta {
+                    names: &[#(#strings),*],
+                    bits: 0 #(| #permissions::#names.bits())*,
+                })
+            }
+        },
+        None => quote!(None),
+    };
+
+    let dm_permission = match dm_permission {
+        Some(dm_permission) => quote!(Some(#dm_permission)),
+        None => quote!(None),
+    };
+
+    quote! {
+        default_member_permissions: #default_member_permissions,
+        dm_permission: #dm_permission,
+    }
+}
+
+/// Generates the metadata of the options, in order.
+fn generate_option_metadata(fields: &[CommandField]) -> TokenStream {
+    let entries = fields.iter().map(|field| match field {
+        CommandField::Option(opt) => {
+            let CommandOption {
+                name,
+                description,
+                kind,
+                required,
+                ..
+            } = opt;
+
+            quote! {
+                serenity_commands::info::OptionEntry::Option(serenity_commands::info::OptionMeta {
+                    name: #name,
+                    description: #description,
+                    kind: serenity_commands::model::OptionKind::#kind,
+                    required: #required,
+                })
+            }
+        },
+        CommandField::Flatten(f) => {
+            let ty = &f.ty;
+
+            quote!(serenity_commands::info::OptionEntry::Flatten(<#ty as serenity_commands::Options>::METADATA))
+        },
+    });
+
+    quote!(&[#(#entries),*])
+}
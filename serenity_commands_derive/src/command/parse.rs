@@ -1,31 +1,43 @@
 use proc_macro2::TokenStream;
-use quote::{quote, ToTokens};
+use quote::{format_ident, quote, ToTokens};
 use syn::spanned::Spanned;
 use syn::*;
 
 use crate::common::{
-    ensure_tuple_variant,
+    get_lit_boolean,
     get_lit_string,
     get_path_as_string,
+    infer_name,
     is_option,
     parse_doc,
+    variant_type,
     AttrOption,
+    RenameRule,
 };
 
 pub struct Command {
     pub name: String,
     pub description: String,
+    pub prefix: bool,
+    /// The permissions given with `default_member_permissions`, as idents
+    /// spanned at the literal.
+    pub default_member_permissions: Option<Vec<Ident>>,
+    pub dm_permission: Option<bool>,
     pub data: CommandData,
 }
 
 pub enum CommandData {
-    Options(Vec<CommandOption>),
-    SubCommands(Vec<SubCommand>),
+    Options(Vec<CommandField>),
+    SubCommands(Vec<SubCommandVariant>),
 }
 
 impl Command {
     pub fn new(input: &DeriveInput) -> Result<Command> {
         let mut name = AttrOption::new("name");
+        let mut prefix = AttrOption::new("prefix");
+        let mut rename_all = AttrOption::new("rename_all");
+        let mut default_member_permissions = AttrOption::new("default_member_permissions");
+        let mut dm_permission = AttrOption::new("dm_permission");
 
         let mut description = None;
 
@@ -56,17 +68,28 @@ impl Command {
                     NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("name") => {
                         name.set(nv.span(), get_lit_string(&nv.lit)?)?;
                     },
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("rename_all") => {
+                        rename_all.set(nv.span(), RenameRule::from_lit(&nv.lit)?)?;
+                    },
+                    NestedMeta::Meta(Meta::Path(p)) if p.is_ident("prefix") => {
+                        prefix.set(p.span(), ())?;
+                    },
+                    NestedMeta::Meta(Meta::NameValue(nv))
+                        if nv.path.is_ident("default_member_permissions") =>
+                    {
+                        default_member_permissions.set(nv.span(), parse_permissions(&nv.lit)?)?;
+                    },
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("dm_permission") => {
+                        dm_permission.set(nv.span(), get_lit_boolean(&nv.lit)?)?;
+                    },
                     _ => return Err(Error::new(meta.span(), "unknown option or invalid syntax")),
                 };
             }
         }
 
-        let name = match name.value() {
-            Some(name) => name,
-            None => {
-                return Err(Error::new(input.ident.span(), "expected a name"));
-            },
-        };
+        let rename_all = rename_all.value();
+
+        let name = infer_name(name, rename_all, &input.ident);
 
         let description = match description {
             Some(desc) => desc,
@@ -79,8 +102,8 @@ impl Command {
         };
 
         let data = match &input.data {
-            Data::Struct(s) => parse_struct(s)?,
-            Data::Enum(e) => parse_enum(e)?,
+            Data::Struct(s) => parse_struct(s, rename_all)?,
+            Data::Enum(e) => parse_enum(e, rename_all)?,
             _ => {
                 return Err(Error::new(
                     input.span(),
@@ -92,39 +115,91 @@ impl Command {
         Ok(Command {
             name,
             description,
+            prefix: prefix.value().is_some(),
+            default_member_permissions: default_member_permissions.value(),
+            dm_permission: dm_permission.value(),
             data,
         })
     }
 }
 
-fn parse_struct(data: &DataStruct) -> Result<CommandData> {
+/// Parses permissions separated by `|`, such as `"BAN_MEMBERS | KICK_MEMBERS"`,
+/// which are checked against the permissions of the library once generated.
+fn parse_permissions(lit: &Lit) -> Result<Vec<Ident>> {
+    get_lit_string(lit)?
+        .split('|')
+        .map(|name| match parse_str::<Ident>(name.trim()) {
+            Ok(ident) => Ok(Ident::new(&ident.to_string(), lit.span())),
+            Err(_) => Err(Error::new(
+                lit.span(),
+                "expected the names of permissions separated by `|`, such as `BAN_MEMBERS`",
+            )),
+        })
+        .collect()
+}
+
+fn parse_struct(data: &DataStruct, rename_all: Option<RenameRule>) -> Result<CommandData> {
     match &data.fields {
         Fields::Unit => Ok(CommandData::Options(Vec::new())),
-        Fields::Named(n) => {
-            if n.named.len() > 25 {
-                return Err(Error::new(
-                    data.fields.span(),
-                    "a command cannot have more than 25 options",
-                ));
-            }
+        Fields::Named(n) => Ok(CommandData::Options(parse_fields(n, rename_all)?)),
+        _ => Err(Error::new(
+            data.fields.span(),
+            "expected a struct with named fields or a unit struct",
+        )),
+    }
+}
 
-            let mut options = Vec::new();
-            for field in &n.named {
-                options.push(CommandOption::new(field)?);
-            }
+pub fn parse_fields(
+    fields: &FieldsNamed,
+    rename_all: Option<RenameRule>,
+) -> Result<Vec<CommandField>> {
+    let mut res = Vec::new();
+    for field in &fields.named {
+        res.push(CommandField::new(field, rename_all)?);
+    }
 
-            Ok(CommandData::Options(options))
-        },
-        _ => {
-            return Err(Error::new(
-                data.fields.span(),
-                "expected a struct with named fields or a unit struct",
-            ));
-        },
+    // Flattened options are only known once the types are checked, so the
+    // limit is enforced again in the generated code.
+    if options(&res).count() > 25 {
+        return Err(Error::new(fields.span(), "a command cannot have more than 25 options"));
     }
+
+    Ok(res)
 }
 
-fn parse_enum(data: &DataEnum) -> Result<CommandData> {
+/// Parses the options of a struct deriving `Options`.
+pub fn parse_option_set(input: &DeriveInput) -> Result<Vec<CommandField>> {
+    let mut rename_all = AttrOption::new("rename_all");
+
+    for attr in &input.attrs {
+        if !attr.path.is_ident("options") {
+            continue;
+        }
+
+        let list = match attr.parse_meta()? {
+            Meta::List(l) => l,
+            _ => return Err(Error::new(attr.span(), "expected a list")),
+        };
+
+        for meta in list.nested {
+            match meta {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("rename_all") => {
+                    rename_all.set(nv.span(), RenameRule::from_lit(&nv.lit)?)?;
+                },
+                _ => return Err(Error::new(meta.span(), "unknown option or invalid syntax")),
+            };
+        }
+    }
+
+    match &input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(n), ..
+        }) => parse_fields(n, rename_all.value()),
+        _ => Err(Error::new(input.span(), "expected a struct with named fields")),
+    }
+}
+
+fn parse_enum(data: &DataEnum, rename_all: Option<RenameRule>) -> Result<CommandData> {
     if data.variants.len() > 25 {
         return Err(Error::new(
             data.variants.span(),
@@ -135,9 +210,7 @@ fn parse_enum(data: &DataEnum) -> Result<CommandData> {
     let mut subcommands = Vec::new();
 
     for variant in &data.variants {
-        ensure_tuple_variant(variant, "subcommand group or subcommand")?;
-
-        subcommands.push(SubCommand::new(variant)?);
+        subcommands.push(SubCommandVariant::new(variant, rename_all)?);
     }
 
     Ok(CommandData::SubCommands(subcommands))
@@ -170,12 +243,34 @@ impl CommandOptionKind {
         })
     }
 
-    pub fn to_data_option_value_extraction(self) -> TokenStream {
+    fn as_str(self) -> &'static str {
         match self {
-            Self::User => quote!(User(v, _)),
-            _ => quote!(#self(v)),
+            Self::Boolean => "boolean",
+            Self::String => "string",
+            Self::Integer => "integer",
+            Self::Number => "number",
+            Self::Mention => "mention",
+            Self::User => "user",
+            Self::Channel => "channel",
+            Self::Role => "role",
         }
     }
+
+    pub fn to_argument_fn(self) -> Ident {
+        format_ident!("{}", self.as_str())
+    }
+
+    /// Returns the function of `serenity_commands::options` parsing the value
+    /// of an option of this kind.
+    pub fn to_parse_fn(self) -> Ident {
+        format_ident!("parse_{}", self.as_str())
+    }
+
+    /// Returns the function of `serenity_commands::options` creating an
+    /// option of this kind.
+    pub fn to_option_fn(self) -> Ident {
+        format_ident!("{}_option", self.as_str())
+    }
 }
 
 impl ToTokens for CommandOptionKind {
@@ -185,15 +280,97 @@ impl ToTokens for CommandOptionKind {
             Self::String => quote!(String),
             Self::Integer => quote!(Integer),
             Self::Number => quote!(Number),
-            Self::Mention => quote!(Mention),
+            Self::Mention => quote!(Mentionable),
             Self::User => quote!(User),
             Self::Channel => quote!(Channel),
             Self::Role => quote!(Role),
         });
     }
 }
+pub enum CommandField {
+    Option(CommandOption),
+    Flatten(FlattenedOptions),
+}
+
+impl CommandField {
+    fn new(field: &Field, rename_all: Option<RenameRule>) -> Result<Self> {
+        if !is_flattened(field)? {
+            return Ok(Self::Option(CommandOption::new(field, rename_all)?));
+        }
+
+        Ok(Self::Flatten(FlattenedOptions {
+            ident: field.ident.clone().expect("named field"),
+            ty: field.ty.clone(),
+        }))
+    }
+
+    pub fn ident(&self) -> &Ident {
+        match self {
+            Self::Option(o) => &o.ident,
+            Self::Flatten(f) => &f.ident,
+        }
+    }
+}
+
+/// Returns the options of a command that are not flattened.
+pub fn options(fields: &[CommandField]) -> impl Iterator<Item = &CommandOption> {
+    fields.iter().filter_map(|f| match f {
+        CommandField::Option(o) => Some(o),
+        CommandField::Flatten(_) => None,
+    })
+}
+
+/// Returns the flattened options of a command.
+pub fn flattened(fields: &[CommandField]) -> impl Iterator<Item = &FlattenedOptions> {
+    fields.iter().filter_map(|f| match f {
+        CommandField::Option(_) => None,
+        CommandField::Flatten(f) => Some(f),
+    })
+}
+
+/// A field holding a set of options that are merged into the command.
+pub struct FlattenedOptions {
+    pub ident: Ident,
+    pub ty: Type,
+}
+
+fn is_flattened(field: &Field) -> Result<bool> {
+    let mut flatten = AttrOption::new("flatten");
+    let mut other = None;
+
+    for attr in &field.attrs {
+        if !attr.path.is_ident("option") {
+            continue;
+        }
+
+        if let Meta::List(list) = attr.parse_meta()? {
+            for meta in list.nested {
+                match &meta {
+                    // `flatten` option
+                    NestedMeta::Meta(Meta::Path(p)) if p.is_ident("flatten") => {
+                        flatten.set(p.span(), ())?;
+                    },
+                    _ => {
+                        other.get_or_insert(meta.span());
+                    },
+                }
+            }
+        }
+    }
+
+    let flatten = flatten.value().is_some();
+
+    match other {
+        Some(span) if flatten => {
+            Err(Error::new(span, "a flattened field cannot have other options"))
+        },
+        _ => Ok(flatten),
+    }
+}
+
 pub struct CommandOption {
     pub ident: Ident,
+    #[allow(dead_code)]
     pub ty: Type,
     pub required: bool,
     pub name: String,
@@ -202,7 +379,7 @@ pub struct CommandOption {
 }
 
 impl CommandOption {
-    fn new(field: &Field) -> Result<Self> {
+    fn new(field: &Field, rename_all: Option<RenameRule>) -> Result<Self> {
         let ident = match &field.ident {
             Some(i) => i.clone(),
             None => {
@@ -267,7 +444,14 @@ impl CommandOption {
             }
         }
 
-        let name = name.value().unwrap_or_else(|| ident.to_string());
+        let name = name.value().unwrap_or_else(|| {
+            let field = ident.to_string();
+
+            match rename_all {
+                Some(rule) => rule.apply_to_field(&field),
+                None => field,
+            }
+        });
 
         let description = match description {
             Some(desc) => desc,
@@ -315,17 +499,24 @@ impl SubCommandKind {
         })
     }
 
-    pub fn to_registration_fn(self) -> TokenStream {
+    pub fn to_parsing_fn(self) -> TokenStream {
         match self {
-            Self::SubCommand => quote!(register_subcommand),
-            Self::Group => quote!(register_subcommand_group),
+            Self::SubCommand => quote!(parse_subcommand),
+            Self::Group => quote!(parse_subcommand_group),
         }
     }
 
-    pub fn to_parsing_fn(self) -> TokenStream {
+    pub fn to_trait(self) -> TokenStream {
         match self {
-            Self::SubCommand => quote!(parse_subcommand),
-            Self::Group => quote!(parse_subcommand_group),
+            Self::SubCommand => quote!(serenity_commands::SubCommand),
+            Self::Group => quote!(serenity_commands::Group),
+        }
+    }
+
+    pub fn to_option_fn(self) -> TokenStream {
+        match self {
+            Self::SubCommand => quote!(to_subcommand_option),
+            Self::Group => quote!(to_subcommand_group_option),
         }
     }
 }
@@ -339,18 +530,29 @@ impl ToTokens for SubCommandKind {
     }
 }
 
-pub struct SubCommand {
-    pub ident: Ident,
-    pub kind: SubCommandKind,
+/// A variant of a command made of subcommands and subcommand groups.
+pub enum SubCommandVariant {
+    /// A tuple variant holding a subcommand or a subcommand group.
+    Type(SubCommand),
+    /// A unit or struct-like variant defining a subcommand in place.
+    Inline(InlineSubCommand),
 }
 
-impl SubCommand {
-    fn new(var: &Variant) -> Result<Self> {
-        let ident = var.ident.clone();
-
+impl SubCommandVariant {
+    fn new(var: &Variant, rename_all: Option<RenameRule>) -> Result<Self> {
+        let mut name = AttrOption::new("name");
         let mut kind = None;
 
+        // Only read for inline subcommands, whose description is their
+        // documentation, with its lines joined.
+        let mut doc = Vec::new();
+
         for attr in &var.attrs {
+            if attr.path.is_ident("doc") {
+                doc.push(attr);
+                continue;
+            }
+
             if !attr.path.is_ident("option") {
                 continue;
             }
@@ -366,6 +568,11 @@ impl SubCommand {
                         return Err(Error::new(meta.span(), "unexpected literal"));
                     },
                     NestedMeta::Meta(m) => match m {
+                        // `name = "..."` option
+                        Meta::NameValue(nv) if nv.path.is_ident("name") => {
+                            name.set(nv.span(), (nv.span(), get_lit_string(&nv.lit)?))?;
+                        },
+
                         // `subcommand` | `group` option
                         Meta::Path(p) => {
                             if kind.is_some() {
@@ -375,7 +582,7 @@ impl SubCommand {
                                 ));
                             }
 
-                            kind = SubCommandKind::new(&get_path_as_string(p)?);
+                            kind = Some((p.span(), SubCommandKind::new(&get_path_as_string(p)?)));
                         },
                         _ => {
                             return Err(Error::new(meta.span(), "unknown option or invalid syntax"))
@@ -385,19 +592,128 @@ impl SubCommand {
             }
         }
 
-        let kind = match kind {
-            Some(kind) => kind,
-            None => {
+        let fields = match &var.fields {
+            Fields::Unnamed(n) if n.unnamed.len() != 1 => {
                 return Err(Error::new(
-                    var.span(),
-                    "expected a type for the option (either `subcommand` or `group`)",
+                    n.span(),
+                    "expected a single subcommand group or subcommand as a field of this tuple struct variant",
                 ));
             },
+            Fields::Unnamed(_) => {
+                if let Some((span, _)) = name.value() {
+                    return Err(Error::new(span, "unknown option or invalid syntax"));
+                }
+
+                let kind = match kind {
+                    Some((_, Some(kind))) => Some(kind),
+                    Some((span, None)) => {
+                        return Err(Error::new(
+                            span,
+                            "expected a type for the option (either `subcommand` or `group`)",
+                        ));
+                    },
+                    None => None,
+                };
+
+                return Ok(Self::Type(SubCommand {
+                    ident: var.ident.clone(),
+                    ty: variant_type(var),
+                    kind,
+                }));
+            },
+            Fields::Named(n) => Some(parse_fields(n, rename_all)?),
+            Fields::Unit => None,
         };
 
-        Ok(Self {
-            ident,
-            kind,
-        })
+        if let Some((span, kind)) = kind {
+            if !matches!(kind, Some(SubCommandKind::SubCommand)) {
+                return Err(Error::new(
+                    span,
+                    "an inline subcommand can only be marked as a `subcommand`",
+                ));
+            }
+        }
+
+        let name = match name.value() {
+            Some((_, name)) => name,
+            None => rename_all.unwrap_or(RenameRule::Snake).apply_to_type(&var.ident.to_string()),
+        };
+
+        let lines = doc.into_iter().map(parse_doc).collect::<Result<Vec<_>>>()?;
+        let description = lines.into_iter().filter(|l| !l.is_empty()).collect::<Vec<_>>().join(" ");
+
+        if description.is_empty() {
+            return Err(Error::new(
+                var.ident.span(),
+                "expected a description in documentation string",
+            ));
+        }
+
+        Ok(Self::Inline(InlineSubCommand {
+            ident: var.ident.clone(),
+            name,
+            description,
+            fields,
+        }))
+    }
+}
+
+pub struct SubCommand {
+    pub ident: Ident,
+    pub ty: Type,
+    /// The kind given with `#[option(...)]`, or `None` to go through
+    /// `SubCommandOption`, which both subcommands and groups implement.
+    pub kind: Option<SubCommandKind>,
+}
+
+impl SubCommand {
+    pub fn to_parsing_fn(&self) -> TokenStream {
+        match self.kind {
+            Some(kind) => kind.to_parsing_fn(),
+            None => quote!(parse_option),
+        }
+    }
+
+    pub fn to_trait(&self) -> TokenStream {
+        match self.kind {
+            Some(kind) => kind.to_trait(),
+            None => quote!(serenity_commands::SubCommandOption),
+        }
+    }
+
+    pub fn to_option_fn(&self) -> TokenStream {
+        match self.kind {
+            Some(kind) => kind.to_option_fn(),
+            None => quote!(to_option),
+        }
+    }
+}
+
+pub struct InlineSubCommand {
+    pub ident: Ident,
+    pub name: String,
+    pub description: String,
+    /// The options of a struct-like variant, or `None` for a unit variant.
+    pub fields: Option<Vec<CommandField>>,
+}
+
+impl InlineSubCommand {
+    pub fn fields(&self) -> &[CommandField] {
+        self.fields.as_deref().unwrap_or_default()
+    }
+
+    /// Returns the tokens constructing or matching this variant, with each
+    /// field bound to a variable of the same name.
+    pub fn pattern(&self) -> TokenStream {
+        let ident = &self.ident;
+
+        match &self.fields {
+            Some(fields) => {
+                let field_idents = fields.iter().map(CommandField::ident);
+
+                quote!(Self::#ident { #(#field_idents),* })
+            },
+            None => quote!(Self::#ident),
+        }
     }
 }
@@ -0,0 +1,89 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::*;
+
+mod parse;
+
+use parse::*;
+
+use crate::backend;
+
+pub fn command_fn(args: TokenStream, item: TokenStream) -> Result<TokenStream> {
+    let item = parse2::<ItemFn>(item)?;
+
+    backend::require_serenity(item.sig.ident.span(), "`#[command]`")?;
+
+    let CommandFn {
+        name,
+        rename_all,
+        ident,
+        item,
+        output,
+        context,
+        defer,
+        options,
+    } = parse_command_fn(args, item)?;
+
+    let rename_all = rename_all.map(|lit| quote!(, rename_all = #lit));
+
+    let vis = &item.vis;
+    let fn_ident = &item.sig.ident;
+    let docs = item.attrs.iter().filter(|a| a.path.is_ident("doc"));
+
+    let option_attrs = options.iter().map(|o| &o.attrs);
+    let option_idents = options.iter().map(|o| &o.ident).collect::<Vec<_>>();
+    let option_types = options.iter().map(|o| &o.ty);
+
+    let interaction = backend::interaction();
+
+    let context_args =
+        if context { quote!(context) } else { quote!(context.ctx, context.interaction) };
+
+    // If the deferral fails, replying through the context still creates the
+    // response.
+    let deferral = match defer {
+        Some(false) => quote!(let _ = context.defer().await;),
+        Some(true) => quote!(let _ = context.defer_ephemeral().await;),
+        None => TokenStream::new(),
+    };
+
+    let output = quote! {
+        #(#docs)*
+        #[derive(Debug, serenity_commands::macros::Command)]
+        #[command(name = #name #rename_all)]
+        #vis struct #ident {
+            #(
+                #(#option_attrs)*
+                #vis #option_idents: #option_types,
+            )*
+        }
+
+        #[serenity_commands::serenity::async_trait]
+        impl serenity_commands::dispatch::Handler for #ident {
+            type Output = #output;
+
+            async fn run(
+                self,
+                ctx: &serenity_commands::serenity::client::Context,
+                interaction: &#interaction,
+            ) -> Self::Output {
+                let context = serenity_commands::context::CommandContext::new(ctx, interaction, ());
+
+                serenity_commands::dispatch::Handler::run_with_context(self, context).await
+            }
+
+            async fn run_with_context(
+                self,
+                context: serenity_commands::context::CommandContext<'_>,
+            ) -> Self::Output {
+                #deferral
+
+                #fn_ident(#context_args, #(self.#option_idents),*).await
+            }
+        }
+
+        #item
+    };
+
+    Ok(output)
+}
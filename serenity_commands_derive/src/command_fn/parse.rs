@@ -0,0 +1,231 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::*;
+
+use crate::backend::Backend;
+use crate::common::{get_lit_string, AttrOption, RenameRule};
+
+pub struct CommandFn {
+    pub name: String,
+    pub rename_all: Option<Lit>,
+    pub ident: Ident,
+    pub item: ItemFn,
+    pub output: Type,
+    /// Whether the function takes a `CommandContext` in place of the context
+    /// and the interaction.
+    pub context: bool,
+    /// Whether to defer the response before running the function, and whether
+    /// to defer it ephemerally.
+    pub defer: Option<bool>,
+    pub options: Vec<FnOption>,
+}
+
+pub struct FnOption {
+    pub attrs: Vec<Attribute>,
+    pub ident: Ident,
+    pub ty: Type,
+}
+
+pub fn parse_command_fn(args: TokenStream, mut item: ItemFn) -> Result<CommandFn> {
+    let mut name = AttrOption::new("name");
+    let mut rename_all = AttrOption::new("rename_all");
+    let mut defer = AttrOption::new("defer");
+
+    let args = Punctuated::<NestedMeta, Token![,]>::parse_terminated.parse2(args)?;
+
+    for meta in args {
+        match meta {
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("name") => {
+                name.set(nv.span(), get_lit_string(&nv.lit)?)?;
+            },
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("rename_all") => {
+                rename_all.set(nv.span(), (RenameRule::from_lit(&nv.lit)?, nv.lit))?;
+            },
+            NestedMeta::Meta(Meta::Path(p)) if p.is_ident("defer") => {
+                defer.set(p.span(), (p.span(), false))?;
+            },
+            NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("defer") => {
+                defer.set(list.span(), (list.span(), parse_defer(&list)?))?;
+            },
+            _ => return Err(Error::new(meta.span(), "unknown option or invalid syntax")),
+        };
+    }
+
+    if item.sig.asyncness.is_none() {
+        return Err(Error::new(item.sig.fn_token.span(), "expected an async function"));
+    }
+
+    let context = takes_context(&item);
+    let skipped = if context { 1 } else { 2 };
+
+    let defer = defer.value();
+
+    // The response state is only known to the `CommandContext`, so replies
+    // made through the interaction would fail once deferred.
+    if let Some((span, _)) = defer {
+        if !context {
+            return Err(Error::new(
+                span,
+                "`defer` requires the function to take a `CommandContext`",
+            ));
+        }
+    }
+
+    if item.sig.inputs.len() < skipped {
+        return Err(Error::new(
+            item.sig.inputs.span(),
+            "expected a context and an interaction as the first two parameters",
+        ));
+    }
+
+    let mut options = Vec::new();
+
+    for input in item.sig.inputs.iter_mut().skip(skipped) {
+        let arg = match input {
+            FnArg::Typed(arg) => arg,
+            FnArg::Receiver(r) => return Err(Error::new(r.span(), "unexpected `self` parameter")),
+        };
+
+        let ident = match &*arg.pat {
+            Pat::Ident(p) => p.ident.clone(),
+            _ => return Err(Error::new(arg.pat.span(), "expected an identifier")),
+        };
+
+        let mut attrs = std::mem::take(&mut arg.attrs);
+
+        if !has_option_kind(&attrs)? {
+            let kind =
+                match infer_option_kind(&arg.ty) {
+                    Some(kind) => kind,
+                    None => return Err(Error::new(
+                        arg.ty.span(),
+                        "cannot infer the type of the option, provide one with `#[option(...)]`",
+                    )),
+                };
+
+            attrs.push(parse_quote!(#[option(#kind)]));
+        }
+
+        options.push(FnOption {
+            attrs,
+            ident,
+            ty: (*arg.ty).clone(),
+        });
+    }
+
+    let output = match &item.sig.output {
+        ReturnType::Default => parse_quote!(()),
+        ReturnType::Type(_, ty) => (**ty).clone(),
+    };
+
+    let rename_all = rename_all.value();
+
+    let name = name.value().unwrap_or_else(|| {
+        let ident = item.sig.ident.to_string();
+
+        match &rename_all {
+            Some((rule, _)) => rule.apply_to_field(&ident),
+            None => ident,
+        }
+    });
+
+    Ok(CommandFn {
+        name,
+        rename_all: rename_all.map(|(_, lit)| lit),
+        ident: Ident::new(&to_pascal_case(&item.sig.ident.to_string()), item.sig.ident.span()),
+        item,
+        output,
+        context,
+        defer: defer.map(|(_, ephemeral)| ephemeral),
+        options,
+    })
+}
+
+/// Parses `defer(ephemeral)`, returning whether the deferral is ephemeral.
+fn parse_defer(list: &MetaList) -> Result<bool> {
+    match list.nested.iter().collect::<Vec<_>>()[..] {
+        [NestedMeta::Meta(Meta::Path(p))] if p.is_ident("ephemeral") => Ok(true),
+        _ => Err(Error::new(list.nested.span(), "expected `ephemeral`")),
+    }
+}
+
+/// Returns whether the first parameter of the function is a `CommandContext`.
+fn takes_context(item: &ItemFn) -> bool {
+    match item.sig.inputs.first() {
+        Some(FnArg::Typed(arg)) => match &*arg.ty {
+            Type::Path(p) => p.path.segments.last().is_some_and(|s| s.ident == "CommandContext"),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Returns whether the `#[option(...)]` attributes of a parameter provide the
+/// type of the option.
+fn has_option_kind(attrs: &[Attribute]) -> Result<bool> {
+    for attr in attrs {
+        if !attr.path.is_ident("option") {
+            continue;
+        }
+
+        if let Meta::List(list) = attr.parse_meta()? {
+            if list.nested.iter().any(|m| matches!(m, NestedMeta::Meta(Meta::Path(_)))) {
+                return Ok(true);
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+/// Infers the type of an option from the type of its parameter.
+fn infer_option_kind(ty: &Type) -> Option<TokenStream> {
+    let segment = match ty {
+        Type::Path(p) => p.path.segments.last()?,
+        _ => return None,
+    };
+
+    if segment.ident == "Option" {
+        return match &segment.arguments {
+            PathArguments::AngleBracketed(args) => match args.args.first()? {
+                GenericArgument::Type(ty) => infer_option_kind(ty),
+                _ => None,
+            },
+            _ => None,
+        };
+    }
+
+    // Serenity 0.12 parses users, channels, roles and mentionables into their
+    // ids, while earlier versions resolve them.
+    let resolved = Backend::get() != Backend::Serenity0_12;
+
+    Some(match &segment.ident.to_string()[..] {
+        "String" => quote!(string),
+        "i64" => quote!(integer),
+        "f64" => quote!(number),
+        "bool" => quote!(boolean),
+        "User" if resolved => quote!(user),
+        "PartialChannel" if resolved => quote!(channel),
+        "Role" if resolved => quote!(role),
+        "UserId" if !resolved => quote!(user),
+        "ChannelId" if !resolved => quote!(channel),
+        "RoleId" if !resolved => quote!(role),
+        "GenericId" if !resolved => quote!(mention),
+        _ => return None,
+    })
+}
+
+fn to_pascal_case(s: &str) -> String {
+    s.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(c) => c.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
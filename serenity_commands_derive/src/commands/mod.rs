@@ -6,57 +6,242 @@ mod parse;
 
 use parse::*;
 
+use crate::common::add_trait_bound;
+use crate::{backend, trace};
+
 pub fn derive_commands(item: TokenStream) -> Result<TokenStream> {
     let input = parse2::<DeriveInput>(item)?;
 
     let Commands {
+        prefix,
+        dispatch,
         commands,
     } = parse_commands(&input)?;
 
     let name = input.ident;
 
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut generics = input.generics;
+
+    for (_, ty) in &commands {
+        add_trait_bound(&mut generics, ty, quote!(serenity_commands::Command));
+
+        if prefix {
+            add_trait_bound(&mut generics, ty, quote!(serenity_commands::message::FromArguments));
+        }
+
+        if dispatch {
+            add_trait_bound(&mut generics, ty, quote!(serenity_commands::dispatch::Handler));
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let (commands, command_types): (Vec<_>, Vec<_>) = commands.into_iter().unzip();
 
     let command_vars = commands
         .iter()
         .map(|s| format_ident!("{}", s.to_string().to_lowercase()))
         .collect::<Vec<_>>();
 
-    let output = quote! {
-        impl #impl_generics #name #ty_generics #where_clause {
+    let message_fns = if prefix {
+        quote! {
+            pub(crate) fn parse_message(
+                content: &str
+            ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+                let mut args = serenity_commands::message::Arguments::new(content)?;
+
+                let name = args.name()?;
+
+                #(if name == <#command_types as serenity_commands::Command>::name() {
+                    let cmd = <#command_types as serenity_commands::message::FromArguments>::parse_arguments(&mut args)?;
+                    args.finish()?;
+
+                    return Ok(Self::#commands(cmd));
+                })*
+
+                Err(serenity_commands::error::ParseError::UnknownCommand(name))
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let interaction = backend::interaction();
+
+    let dispatch_impl = if dispatch {
+        let first = &command_types[0];
+
+        quote! {
+            #[serenity_commands::serenity::async_trait]
+            impl #impl_generics serenity_commands::dispatch::Handler for #name #ty_generics #where_clause {
+                type Output = <#first as serenity_commands::dispatch::Handler>::Output;
+
+                async fn run(
+                    self,
+                    ctx: &serenity_commands::serenity::client::Context,
+                    interaction: &#interaction,
+                ) -> Self::Output {
+                    match self {
+                        #(Self::#commands(cmd) => serenity_commands::dispatch::Handler::run(cmd, ctx, interaction).await,)*
+                    }
+                }
+
+                async fn run_with_context(
+                    self,
+                    context: serenity_commands::context::CommandContext<'_>,
+                ) -> Self::Output {
+                    match self {
+                        #(Self::#commands(cmd) => serenity_commands::dispatch::Handler::run_with_context(cmd, context).await,)*
+                    }
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let registered_command = backend::registered_command();
+    let register_command = backend::builder_fn("command");
+
+    let registration = if backend::Backend::get() == backend::Backend::Twilight {
+        let command_builder = backend::command_builder();
+
+        // Twilight has no client to register the commands with.
+        quote! {
+            pub(crate) fn create_commands() -> Vec<#command_builder> {
+                vec![#(<#command_types as serenity_commands::Command>::#register_command()),*]
+            }
+        }
+    } else if backend::value_builders() {
+        let command_builder = backend::command_builder();
+
+        let register_globally = trace::register(
+            quote!(None),
+            quote!(#registered_command::set_global_commands(ctx, Self::create_commands())),
+        );
+        let register_in_guild = trace::register(
+            quote!(Some(guild_id)),
+            quote!(guild_id.set_commands(ctx, Self::create_commands())),
+        );
+
+        quote! {
+            fn create_commands() -> Vec<#command_builder> {
+                vec![#(<#command_types as serenity_commands::Command>::#register_command()),*]
+            }
+
+            pub(crate) async fn register_commands_globally(
+                ctx: &serenity_commands::serenity::client::Context
+            ) -> serenity_commands::serenity::Result<Vec<#registered_command>> {
+                #register_globally.await
+            }
+
+            pub(crate) async fn register_commands_in_guild(
+                ctx: &serenity_commands::serenity::client::Context,
+                guild_id: serenity_commands::serenity::model::id::GuildId,
+            ) -> serenity_commands::serenity::Result<Vec<#registered_command>> {
+                #register_in_guild.await
+            }
+        }
+    } else {
+        let register_globally = trace::register(
+            quote!(None),
+            quote!(#registered_command::set_global_application_commands(ctx, Self::register_commands)),
+        );
+        let register_in_guild = trace::register(
+            quote!(Some(guild_id)),
+            quote!(guild_id.set_application_commands(ctx, Self::register_commands)),
+        );
+
+        quote! {
             fn register_commands(
                 cmds: &mut serenity_commands::serenity::builder::CreateApplicationCommands
             ) -> &mut serenity_commands::serenity::builder::CreateApplicationCommands {
-                #(cmds.create_application_command(#commands::register_command);)*
+                #(cmds.create_application_command(<#command_types as serenity_commands::Command>::#register_command);)*
                 cmds
             }
 
             pub(crate) async fn register_commands_globally(
                 ctx: &serenity_commands::serenity::client::Context
-            ) -> serenity_commands::serenity::Result<Vec<serenity_commands::serenity::model::interactions::application_command::ApplicationCommand>> {
-                use serenity_commands::serenity::model::interactions::application_command::ApplicationCommand;
-
-                ApplicationCommand::set_global_application_commands(ctx, Self::register_commands).await
+            ) -> serenity_commands::serenity::Result<Vec<#registered_command>> {
+                #register_globally.await
             }
 
             pub(crate) async fn register_commands_in_guild(
                 ctx: &serenity_commands::serenity::client::Context,
                 guild_id: serenity_commands::serenity::model::id::GuildId,
-            ) -> serenity_commands::serenity::Result<Vec<serenity_commands::serenity::model::interactions::application_command::ApplicationCommand>> {
-                guild_id.set_application_commands(ctx, Self::register_commands).await
+            ) -> serenity_commands::serenity::Result<Vec<#registered_command>> {
+                #register_in_guild.await
             }
+        }
+    };
+
+    let interaction_var = format_ident!("interaction");
+    let data = backend::interaction_data(&interaction_var);
+
+    let parse = trace::parse(&interaction_var, quote! {
+        match &#data.name[..] {
+            #(s if s == #command_vars => <#command_types as serenity_commands::Command>::parse_command(#data).map(Self::#commands),)*
+            s => Err(serenity_commands::error::ParseError::UnknownCommand(s.to_string())),
+        }
+    });
+
+    let value = format_ident!("value");
+    let deserialization = backend::deserialize_interaction(&value);
+
+    let output = quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #registration
 
             pub(crate) fn parse(
-                interaction: serenity_commands::serenity::model::interactions::application_command::ApplicationCommandInteraction
+                interaction: #interaction
             ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
-                #(let #command_vars = #commands::name();)*
+                #(let #command_vars = <#command_types as serenity_commands::Command>::name();)*
 
-                match &interaction.data.name[..] {
-                    #(s if s == #command_vars => #commands::parse_command(interaction.data).map(Self::#commands),)*
-                    s => Err(serenity_commands::error::ParseError::UnknownCommand(s.to_string())),
-                }
+                #parse
             }
+
+            pub(crate) fn parse_json(
+                value: &serenity_commands::serde_json::Value
+            ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+                Self::parse(#deserialization?)
+            }
+
+            pub(crate) fn from_slice(
+                bytes: &[u8]
+            ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+                let value = serenity_commands::serde_json::from_slice(bytes)
+                    .map_err(|e| serenity_commands::error::ParseError::InvalidPayload(e.to_string()))?;
+
+                Self::parse_json(&value)
+            }
+
+            pub(crate) fn metadata() -> impl Iterator<Item = &'static serenity_commands::info::CommandMeta> {
+                <Self as serenity_commands::Commands>::METADATA.iter().copied()
+            }
+
+            pub(crate) fn definitions() -> Vec<serenity_commands::model::CommandDefinition> {
+                vec![#(<#command_types as serenity_commands::Command>::definition()),*]
+            }
+
+            #message_fns
         }
+
+        impl #impl_generics serenity_commands::Commands for #name #ty_generics #where_clause {
+            const METADATA: &'static [&'static serenity_commands::info::CommandMeta] =
+                &[#(<#command_types as serenity_commands::Command>::METADATA),*];
+
+            fn parse(
+                interaction: #interaction
+            ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+                Self::parse(interaction)
+            }
+
+            fn definitions() -> Vec<serenity_commands::model::CommandDefinition> {
+                Self::definitions()
+            }
+        }
+
+        #dispatch_impl
     };
 
     Ok(output)
@@ -2,31 +2,69 @@ use proc_macro2::Ident;
 use syn::spanned::Spanned;
 use syn::*;
 
-use crate::common::ensure_tuple_variant;
+use crate::backend;
+use crate::common::{ensure_tuple_variant, variant_type, AttrOption};
 
 pub struct Commands {
-    pub commands: Vec<Ident>,
+    pub prefix: bool,
+    pub dispatch: bool,
+    pub commands: Vec<(Ident, Type)>,
 }
 
 pub fn parse_commands(input: &DeriveInput) -> Result<Commands> {
+    let mut prefix = AttrOption::new("prefix");
+    let mut dispatch = AttrOption::new("dispatch");
+
+    for attr in &input.attrs {
+        if !attr.path.is_ident("commands") {
+            continue;
+        }
+
+        let list = match attr.parse_meta()? {
+            Meta::List(l) => l,
+            _ => return Err(Error::new(attr.span(), "expected a list")),
+        };
+
+        for meta in list.nested {
+            match meta {
+                NestedMeta::Meta(Meta::Path(p)) if p.is_ident("prefix") => {
+                    prefix.set(p.span(), ())?;
+                },
+                NestedMeta::Meta(Meta::Path(p)) if p.is_ident("dispatch") => {
+                    backend::require_serenity(p.span(), "`#[commands(dispatch)]`")?;
+                    dispatch.set(p.span(), ())?;
+                },
+                _ => return Err(Error::new(meta.span(), "unknown option or invalid syntax")),
+            };
+        }
+    }
+
     let commands = match &input.data {
         Data::Enum(e) => parse_enum(e)?,
         _ => return Err(Error::new(input.span(), "expected an enum")),
     };
 
+    let dispatch = dispatch.value().is_some();
+
+    if dispatch && commands.is_empty() {
+        return Err(Error::new(input.ident.span(), "expected at least one command to dispatch to"));
+    }
+
     Ok(Commands {
+        prefix: prefix.value().is_some(),
+        dispatch,
         commands,
     })
 }
 
-fn parse_enum(data: &DataEnum) -> Result<Vec<Ident>> {
-    let mut idents = Vec::new();
+fn parse_enum(data: &DataEnum) -> Result<Vec<(Ident, Type)>> {
+    let mut commands = Vec::new();
 
     for variant in &data.variants {
         ensure_tuple_variant(variant, "command")?;
 
-        idents.push(variant.ident.clone());
+        commands.push((variant.ident.clone(), variant_type(variant)));
     }
 
-    Ok(idents)
+    Ok(commands)
 }
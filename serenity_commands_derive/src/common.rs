@@ -1,6 +1,10 @@
-use proc_macro2::{Ident, Span};
+use std::fmt::Display;
+use std::str::FromStr;
+
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+use quote::ToTokens;
 use syn::spanned::Spanned;
-use syn::{Attribute, Fields, Lit, Meta, Path, Type, Variant};
+use syn::{Attribute, Fields, Generics, Lit, Meta, Path, Type, Variant, WherePredicate};
 use syn::{Error, Result};
 
 pub struct AttrOption<T> {
@@ -41,7 +45,6 @@ pub fn get_lit_string(lit: &Lit) -> Result<String> {
     }
 }
 
-#[allow(dead_code)]
 pub fn get_lit_boolean(lit: &Lit) -> Result<bool> {
     match lit {
         Lit::Bool(b) => Ok(b.value()),
@@ -49,6 +52,92 @@ pub fn get_lit_boolean(lit: &Lit) -> Result<bool> {
     }
 }
 
+pub fn get_lit_int<N>(lit: &Lit) -> Result<N>
+where
+    N: FromStr,
+    N::Err: Display,
+{
+    match lit {
+        Lit::Int(i) => i.base10_parse(),
+        _ => Err(Error::new(lit.span(), "expected an integer literal")),
+    }
+}
+
+/// A casing convention for the names of commands, groups and options.
+///
+/// Options cannot have choices yet, so there are no choice enums whose
+/// variants would be converted as well.
+#[derive(Clone, Copy)]
+pub enum RenameRule {
+    Lower,
+    Snake,
+    Kebab,
+}
+
+impl RenameRule {
+    pub fn from_lit(lit: &Lit) -> Result<Self> {
+        Ok(match &get_lit_string(lit)?[..] {
+            "lowercase" => Self::Lower,
+            "snake_case" => Self::Snake,
+            "kebab-case" => Self::Kebab,
+            _ => {
+                return Err(Error::new(
+                    lit.span(),
+                    "expected a casing convention (`lowercase`, `snake_case` or `kebab-case`)",
+                ))
+            },
+        })
+    }
+
+    /// Converts the name of a field, assumed to be in snake case.
+    pub fn apply_to_field(self, field: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => field.to_string(),
+            Self::Kebab => field.replace('_', "-"),
+        }
+    }
+
+    /// Converts the name of a type or a variant, assumed to be in pascal case.
+    pub fn apply_to_type(self, ty: &str) -> String {
+        if let Self::Lower = self {
+            return ty.to_ascii_lowercase();
+        }
+
+        let chars = ty.chars().collect::<Vec<_>>();
+
+        // Uppercase letters start words, except within acronyms such as `HTTP`
+        // in `HTTPServer`, where they only do when followed by a lowercase
+        // letter.
+        let mut snake = String::new();
+        for (i, &c) in chars.iter().enumerate() {
+            if c.is_uppercase() && i != 0 {
+                let prev = chars[i - 1];
+                let next = chars.get(i + 1);
+
+                if !prev.is_uppercase() || matches!(next, Some(n) if n.is_lowercase()) {
+                    snake.push('_');
+                }
+            }
+
+            snake.push(c.to_ascii_lowercase());
+        }
+
+        self.apply_to_field(&snake)
+    }
+}
+
+/// Returns the name of a command or group, which is either given by `name`,
+/// or inferred from the name of its type.
+pub fn infer_name(
+    name: AttrOption<String>,
+    rename_all: Option<RenameRule>,
+    ident: &Ident,
+) -> String {
+    name.value().unwrap_or_else(|| {
+        rename_all.unwrap_or(RenameRule::Snake).apply_to_type(&ident.to_string())
+    })
+}
+
 pub fn get_path_as_string(p: &Path) -> Result<String> {
     p.get_ident()
         .map(Ident::to_string)
@@ -100,3 +189,49 @@ pub fn ensure_tuple_variant(variant: &Variant, entity: &str) -> Result<()> {
         },
     }
 }
+
+/// Returns the type of the single field of a tuple variant, which must have
+/// been checked with [`ensure_tuple_variant`].
+pub fn variant_type(variant: &Variant) -> Type {
+    variant.fields.iter().next().expect("tuple variant").ty.clone()
+}
+
+fn mentions_any(tokens: TokenStream, idents: &[&Ident]) -> bool {
+    tokens.into_iter().any(|t| match t {
+        TokenTree::Ident(i) => idents.contains(&&i),
+        TokenTree::Group(g) => mentions_any(g.stream(), idents),
+        _ => false,
+    })
+}
+
+/// Returns whether `ty` mentions one of the type parameters of `generics`.
+pub fn is_generic(ty: &Type, generics: &Generics) -> bool {
+    let params = generics.type_params().map(|p| &p.ident).collect::<Vec<_>>();
+
+    mentions_any(ty.to_token_stream(), &params)
+}
+
+/// Adds a `ty: bound` predicate to `generics` if `ty` mentions one of its
+/// type parameters, so that the generated code can rely on `bound`.
+pub fn add_trait_bound(generics: &mut Generics, ty: &Type, bound: TokenStream) {
+    if !is_generic(ty, generics) {
+        return;
+    }
+
+    let predicate: WherePredicate = syn::parse_quote!(#ty: #bound);
+    generics.make_where_clause().predicates.push(predicate);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RenameRule;
+
+    #[test]
+    fn splits_types_into_words() {
+        assert_eq!(RenameRule::Snake.apply_to_type("ResetPrefix"), "reset_prefix");
+        assert_eq!(RenameRule::Snake.apply_to_type("HTTPServer"), "http_server");
+        assert_eq!(RenameRule::Kebab.apply_to_type("GetURL"), "get-url");
+        assert_eq!(RenameRule::Kebab.apply_to_type("Ipv4Address"), "ipv4-address");
+        assert_eq!(RenameRule::Lower.apply_to_type("HTTPServer"), "httpserver");
+    }
+}
@@ -0,0 +1,276 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::*;
+
+mod parse;
+
+use parse::*;
+
+use crate::backend;
+
+pub fn derive_component(item: TokenStream) -> Result<TokenStream> {
+    let input = parse2::<DeriveInput>(item)?;
+
+    backend::require_serenity(input.ident.span(), "`#[derive(Component)]`")?;
+
+    let Component {
+        variants,
+    } = parse_component(&input)?;
+
+    let name = input.ident;
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let custom_id_arms = variants.iter().map(generate_custom_id_arm);
+    let parsing_arms = variants.iter().map(generate_parsing_arm);
+    let button_arms = variants.iter().map(generate_button_arm);
+    let select_menu_arms = variants.iter().map(generate_select_menu_arm);
+
+    let separator = quote!(serenity_commands::component::SEPARATOR);
+
+    let component_interaction = backend::component_interaction();
+    let component_data = backend::component_data();
+    let button_style = backend::button_style();
+
+    // Serenity 0.12 has the values of select menus in the kind of the data,
+    // with the ids of the selected entities as ids rather than strings, and
+    // builders that need the kind of select menu upfront.
+    let (values, builders) = if backend::value_builders() {
+        let values = quote! {{
+            use serenity_commands::serenity::model::application::ComponentInteractionDataKind as Kind;
+
+            fn to_strings<T: std::string::ToString>(values: Vec<T>) -> Vec<String> {
+                values.iter().map(T::to_string).collect()
+            }
+
+            match data.kind {
+                Kind::StringSelect { values } => values,
+                Kind::UserSelect { values } => to_strings(values),
+                Kind::RoleSelect { values } => to_strings(values),
+                Kind::MentionableSelect { values } => to_strings(values),
+                Kind::ChannelSelect { values } => to_strings(values),
+                _ => Vec::new(),
+            }
+        }};
+
+        let builders = quote! {
+            pub(crate) fn create_button(
+                &self
+            ) -> std::result::Result<serenity_commands::serenity::builder::CreateButton, serenity_commands::error::ParseError> {
+                #[allow(unused_imports)]
+                use #button_style;
+
+                #[allow(unused_mut)]
+                let mut button = serenity_commands::serenity::builder::CreateButton::new(self.custom_id()?);
+
+                match self {
+                    #(#button_arms)*
+                }
+
+                Ok(button)
+            }
+
+            pub(crate) fn create_select_menu(
+                &self,
+                kind: serenity_commands::serenity::builder::CreateSelectMenuKind
+            ) -> std::result::Result<serenity_commands::serenity::builder::CreateSelectMenu, serenity_commands::error::ParseError> {
+                #[allow(unused_mut)]
+                let mut menu = serenity_commands::serenity::builder::CreateSelectMenu::new(self.custom_id()?, kind);
+
+                match self {
+                    #(#select_menu_arms)*
+                }
+
+                Ok(menu)
+            }
+        };
+
+        (values, builders)
+    } else {
+        let builders = quote! {
+            pub(crate) fn create_button<'a>(
+                &self,
+                button: &'a mut serenity_commands::serenity::builder::CreateButton
+            ) -> std::result::Result<&'a mut serenity_commands::serenity::builder::CreateButton, serenity_commands::error::ParseError> {
+                #[allow(unused_imports)]
+                use #button_style;
+
+                button.custom_id(self.custom_id()?);
+
+                match self {
+                    #(#button_arms)*
+                }
+
+                Ok(button)
+            }
+
+            pub(crate) fn create_select_menu<'a>(
+                &self,
+                menu: &'a mut serenity_commands::serenity::builder::CreateSelectMenu
+            ) -> std::result::Result<&'a mut serenity_commands::serenity::builder::CreateSelectMenu, serenity_commands::error::ParseError> {
+                menu.custom_id(self.custom_id()?);
+
+                match self {
+                    #(#select_menu_arms)*
+                }
+
+                Ok(menu)
+            }
+        };
+
+        (quote!(data.values), builders)
+    };
+
+    let output = quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns the custom id of the component, failing if it is longer
+            /// than Discord allows.
+            pub(crate) fn custom_id(&self) -> std::result::Result<String, serenity_commands::error::ParseError> {
+                let custom_id = match self {
+                    #(#custom_id_arms)*
+                };
+
+                serenity_commands::component::check_length(custom_id)
+            }
+
+            pub(crate) fn parse(
+                interaction: #component_interaction
+            ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+                Self::parse_data(interaction.data)
+            }
+
+            pub(crate) fn parse_data(
+                data: #component_data
+            ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+                let custom_id = data.custom_id;
+                #[allow(unused_variables)]
+                let values = #values;
+
+                let (id, fields) = match custom_id.split_once(#separator) {
+                    Some((id, fields)) => (id, Some(fields)),
+                    None => (&custom_id[..], None),
+                };
+
+                let invalid = || serenity_commands::error::ParseError::InvalidCustomId(custom_id.clone());
+
+                match id {
+                    #(#parsing_arms)*
+                    id => Err(serenity_commands::error::ParseError::UnknownComponent(id.to_string())),
+                }
+            }
+
+            #builders
+        }
+    };
+
+    Ok(output)
+}
+
+fn generate_custom_id_arm(variant: &ComponentVariant) -> TokenStream {
+    let id = &variant.id;
+
+    let pattern = variant.destructure(|f| {
+        if f.values {
+            quote!(_)
+        } else {
+            let binding = &f.binding;
+            quote!(#binding)
+        }
+    });
+
+    let bindings = variant.id_fields().map(|f| &f.binding);
+
+    quote! {
+        #pattern => {
+            #[allow(unused_mut)]
+            let mut custom_id = String::from(#id);
+            #(
+                custom_id.push(serenity_commands::component::SEPARATOR);
+                custom_id.push_str(&serenity_commands::component::escape(&#bindings.to_string()));
+            )*
+            custom_id
+        },
+    }
+}
+
+fn generate_parsing_arm(variant: &ComponentVariant) -> TokenStream {
+    let id = &variant.id;
+
+    let count = variant.id_fields().count();
+
+    let constructor = variant.destructure(|f| {
+        if f.values {
+            quote!(values)
+        } else {
+            let binding = &f.binding;
+            quote!(#binding)
+        }
+    });
+
+    if count == 0 {
+        return quote! {
+            #id => match fields {
+                None => Ok(#constructor),
+                Some(_) => Err(invalid()),
+            },
+        };
+    }
+
+    let bindings = variant.id_fields().map(|f| &f.binding);
+
+    quote! {
+        #id => {
+            let mut fields = fields
+                .ok_or_else(invalid)?
+                .splitn(#count, serenity_commands::component::SEPARATOR);
+
+            #(
+                let #bindings = serenity_commands::component::unescape(fields.next().ok_or_else(invalid)?)
+                    .parse()
+                    .map_err(|_| invalid())?;
+            )*
+
+            Ok(#constructor)
+        },
+    }
+}
+
+fn generate_button_arm(variant: &ComponentVariant) -> TokenStream {
+    let ident = &variant.ident;
+
+    let label = variant.label.as_ref().map(|label| build(quote!(button), quote!(label(#label))));
+    let style =
+        variant.style.map(|style| build(quote!(button), quote!(style(ButtonStyle::#style))));
+
+    quote! {
+        Self::#ident { .. } => {
+            #label
+            #style
+        },
+    }
+}
+
+fn generate_select_menu_arm(variant: &ComponentVariant) -> TokenStream {
+    let ident = &variant.ident;
+
+    let placeholder = variant
+        .placeholder
+        .as_ref()
+        .map(|placeholder| build(quote!(menu), quote!(placeholder(#placeholder))));
+
+    quote! {
+        Self::#ident { .. } => {
+            #placeholder
+        },
+    }
+}
+
+/// Generates the call of `method` on `builder`, which is reassigned when
+/// builders are values.
+fn build(builder: TokenStream, method: TokenStream) -> TokenStream {
+    if backend::value_builders() {
+        quote!(#builder = #builder.#method;)
+    } else {
+        quote!(#builder.#method;)
+    }
+}
@@ -0,0 +1,241 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::spanned::Spanned;
+use syn::*;
+
+use crate::common::{get_lit_string, get_path_as_string, AttrOption};
+
+/// The separator between the id of a component and its fields in a custom id,
+/// as in `serenity_commands::component::SEPARATOR`.
+const SEPARATOR: char = ':';
+
+/// The maximum number of characters in a custom id.
+const MAX_CUSTOM_ID_LENGTH: usize = 100;
+
+pub struct Component {
+    pub variants: Vec<ComponentVariant>,
+}
+
+pub fn parse_component(input: &DeriveInput) -> Result<Component> {
+    let data = match &input.data {
+        Data::Enum(e) => e,
+        _ => return Err(Error::new(input.span(), "expected an enum")),
+    };
+
+    let mut variants: Vec<ComponentVariant> = Vec::new();
+
+    for variant in &data.variants {
+        let variant = ComponentVariant::new(variant)?;
+
+        if variants.iter().any(|v| v.id == variant.id) {
+            return Err(Error::new(
+                variant.span,
+                format_args!("component id \"{}\" has already been used", variant.id),
+            ));
+        }
+
+        variants.push(variant);
+    }
+
+    Ok(Component {
+        variants,
+    })
+}
+
+#[derive(Clone, Copy)]
+pub enum ButtonStyleKind {
+    Primary,
+    Secondary,
+    Success,
+    Danger,
+}
+
+impl ButtonStyleKind {
+    fn new(s: &str) -> Option<Self> {
+        Some(match s {
+            "primary" => Self::Primary,
+            "secondary" => Self::Secondary,
+            "success" => Self::Success,
+            "danger" => Self::Danger,
+            _ => return None,
+        })
+    }
+}
+
+impl ToTokens for ButtonStyleKind {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            Self::Primary => quote!(Primary),
+            Self::Secondary => quote!(Secondary),
+            Self::Success => quote!(Success),
+            Self::Danger => quote!(Danger),
+        });
+    }
+}
+
+pub struct ComponentField {
+    pub member: Member,
+    pub binding: Ident,
+    pub values: bool,
+}
+
+pub struct ComponentVariant {
+    pub ident: Ident,
+    pub span: Span,
+    pub id: String,
+    pub label: Option<String>,
+    pub style: Option<ButtonStyleKind>,
+    pub placeholder: Option<String>,
+    pub named: bool,
+    pub fields: Vec<ComponentField>,
+}
+
+impl ComponentVariant {
+    fn new(var: &Variant) -> Result<Self> {
+        let mut id = AttrOption::new("id");
+        let mut label = AttrOption::new("label");
+        let mut style = AttrOption::new("style");
+        let mut placeholder = AttrOption::new("placeholder");
+
+        for attr in &var.attrs {
+            if !attr.path.is_ident("component") {
+                continue;
+            }
+
+            let list = match attr.parse_meta()? {
+                Meta::List(l) => l,
+                _ => return Err(Error::new(attr.span(), "expected a list")),
+            };
+
+            for meta in list.nested {
+                match meta {
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("id") => {
+                        id.set(nv.span(), get_lit_string(&nv.lit)?)?;
+                    },
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("label") => {
+                        label.set(nv.span(), get_lit_string(&nv.lit)?)?;
+                    },
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("style") => {
+                        let kind = match ButtonStyleKind::new(&get_lit_string(&nv.lit)?) {
+                            Some(kind) => kind,
+                            None => {
+                                return Err(Error::new(
+                                    nv.lit.span(),
+                                    "expected a button style (`primary`, `secondary`, `success` or `danger`)",
+                                ))
+                            },
+                        };
+
+                        style.set(nv.span(), kind)?;
+                    },
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("placeholder") => {
+                        placeholder.set(nv.span(), get_lit_string(&nv.lit)?)?;
+                    },
+                    _ => return Err(Error::new(meta.span(), "unknown option or invalid syntax")),
+                };
+            }
+        }
+
+        let id = match id.value() {
+            Some(id) => id,
+            None => return Err(Error::new(var.span(), "expected an id")),
+        };
+
+        if id.contains(SEPARATOR) {
+            return Err(Error::new(
+                var.span(),
+                format_args!("a component id cannot contain `{}`", SEPARATOR),
+            ));
+        }
+
+        if id.chars().count() > MAX_CUSTOM_ID_LENGTH {
+            return Err(Error::new(
+                var.span(),
+                format_args!(
+                    "a component id cannot be longer than {} characters",
+                    MAX_CUSTOM_ID_LENGTH
+                ),
+            ));
+        }
+
+        let mut fields = Vec::new();
+
+        for (i, field) in var.fields.iter().enumerate() {
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(Index::from(i)),
+            };
+
+            let values = parse_field_attrs(field)?;
+
+            if values && fields.iter().any(|f: &ComponentField| f.values) {
+                return Err(Error::new(field.span(), "`values` field has already been provided"));
+            }
+
+            fields.push(ComponentField {
+                member,
+                binding: format_ident!("field_{}", i),
+                values,
+            });
+        }
+
+        Ok(Self {
+            ident: var.ident.clone(),
+            span: var.span(),
+            id,
+            label: label.value(),
+            style: style.value(),
+            placeholder: placeholder.value(),
+            named: matches!(var.fields, Fields::Named(_)),
+            fields,
+        })
+    }
+
+    /// Returns the fields that are encoded in the custom id.
+    pub fn id_fields(&self) -> impl Iterator<Item = &ComponentField> {
+        self.fields.iter().filter(|f| !f.values)
+    }
+
+    /// Returns a pattern or a constructor of the variant, binding each field
+    /// to the result of `f`.
+    pub fn destructure(&self, f: impl Fn(&ComponentField) -> TokenStream) -> TokenStream {
+        let ident = &self.ident;
+        let members = self.fields.iter().map(|f| &f.member);
+        let values = self.fields.iter().map(f);
+
+        if self.named {
+            quote!(Self::#ident { #(#members: #values),* })
+        } else if self.fields.is_empty() {
+            quote!(Self::#ident)
+        } else {
+            quote!(Self::#ident(#(#values),*))
+        }
+    }
+}
+
+fn parse_field_attrs(field: &Field) -> Result<bool> {
+    let mut values = false;
+
+    for attr in &field.attrs {
+        if !attr.path.is_ident("component") {
+            continue;
+        }
+
+        let list = match attr.parse_meta()? {
+            Meta::List(l) => l,
+            _ => return Err(Error::new(attr.span(), "expected a list")),
+        };
+
+        for meta in list.nested {
+            match &meta {
+                // `values` option
+                NestedMeta::Meta(Meta::Path(p)) if get_path_as_string(p)? == "values" => {
+                    values = true;
+                },
+                _ => return Err(Error::new(meta.span(), "unknown option or invalid syntax")),
+            }
+        }
+    }
+
+    Ok(values)
+}
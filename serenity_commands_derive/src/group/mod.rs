@@ -6,18 +6,36 @@ mod parse;
 
 use parse::*;
 
+use crate::backend;
+use crate::common::add_trait_bound;
+
 pub fn derive_group(item: TokenStream) -> Result<TokenStream> {
     let input = parse2::<DeriveInput>(item)?;
 
     let Group {
         name: group,
         description,
+        prefix,
         subcommands,
     } = parse_group(&input)?;
 
     let name = input.ident;
 
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut generics = input.generics;
+
+    for (_, ty) in &subcommands {
+        add_trait_bound(&mut generics, ty, quote!(serenity_commands::SubCommand));
+
+        if prefix {
+            add_trait_bound(&mut generics, ty, quote!(serenity_commands::message::FromArguments));
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let (subcommands, subcommand_types): (Vec<_>, Vec<_>) = subcommands.into_iter().unzip();
+
+    let interaction_option = quote!(serenity_commands::model::InteractionOption);
 
     let extra = {
         let mut extra = TokenStream::new();
@@ -27,44 +45,94 @@ pub fn derive_group(item: TokenStream) -> Result<TokenStream> {
             .map(|s| format_ident!("{}", s.to_string().to_lowercase()))
             .collect::<Vec<_>>();
 
+        let group_registration = backend::option_fn(
+            quote!(pub(crate)),
+            &backend::builder_fn("subcommand_group"),
+            quote!(serenity_commands::model::OptionDefinition::from(Self::METADATA)),
+        );
+
         extra.extend(quote! {
-            pub(crate) fn register_subcommand_group(
-                opt: &mut serenity_commands::serenity::builder::CreateApplicationCommandOption
-            ) -> &mut serenity_commands::serenity::builder::CreateApplicationCommandOption {
-                use serenity_commands::serenity::model::interactions::application_command::ApplicationCommandOptionType;
-
-                opt.name(Self::name())
-                    .description(Self::description())
-                    .kind(ApplicationCommandOptionType::SubCommandGroup)
-                    #(.create_sub_option(#subcommands::register_subcommand))*
-            }
+            #group_registration
 
             pub(crate) fn parse_subcommand_group(
-                option: serenity_commands::serenity::model::interactions::application_command::ApplicationCommandInteractionDataOption
+                option: #interaction_option
             ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
                 if option.name != Self::name() {
                     return Err(serenity_commands::error::ParseError::UnknownSubCommandGroup(option.name.clone()));
                 }
 
-                #(let #subcommand_vars = #subcommands::name();)*
+                #(let #subcommand_vars = <#subcommand_types as serenity_commands::SubCommand>::name();)*
 
-                for opt in option.options {
+                for opt in serenity_commands::options::sub_options(option) {
                     #(if opt.name == #subcommand_vars {
-                        return Ok(Self::#subcommands(#subcommands::parse_subcommand(opt)?));
+                        return Ok(Self::#subcommands(<#subcommand_types as serenity_commands::SubCommand>::parse_subcommand(opt)?));
                     })*
 
                     return Err(serenity_commands::error::ParseError::UnknownSubCommand(opt.name.clone()));
                 }
 
-                unreachable!()
+                Err(serenity_commands::error::ParseError::MissingName)
+            }
+
+            pub(crate) fn to_subcommand_group_option(&self) -> #interaction_option {
+                let option = match self {
+                    #(Self::#subcommands(v) => <#subcommand_types as serenity_commands::SubCommand>::to_subcommand_option(v),)*
+                };
+
+                serenity_commands::options::subcommand_group_option(Self::name(), vec![option])
             }
         });
 
+        if prefix {
+            extra.extend(quote! {
+                pub(crate) fn parse_arguments(
+                    args: &mut serenity_commands::message::Arguments
+                ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+                    let name = args.name()?;
+
+                    #(if name == <#subcommand_types as serenity_commands::SubCommand>::name() {
+                        return Ok(Self::#subcommands(
+                            <#subcommand_types as serenity_commands::message::FromArguments>::parse_arguments(args)?
+                        ));
+                    })*
+
+                    Err(serenity_commands::error::ParseError::UnknownSubCommand(name))
+                }
+            });
+        }
+
         extra
     };
 
+    let message_impl = if prefix {
+        quote! {
+            impl #impl_generics serenity_commands::message::FromArguments for #name #ty_generics #where_clause {
+                fn parse_arguments(
+                    args: &mut serenity_commands::message::Arguments
+                ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+                    Self::parse_arguments(args)
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let register_group = backend::builder_fn("subcommand_group");
+    let group_fn = backend::delegate_option_fn(&register_group, &register_group);
+    let option_fn = backend::delegate_option_fn(&backend::builder_fn("option"), &register_group);
+
     let output = quote! {
         impl #impl_generics #name #ty_generics #where_clause {
+            pub(crate) const METADATA: &'static serenity_commands::info::CommandMeta = &serenity_commands::info::CommandMeta {
+                name: #group,
+                description: #description,
+                options: &[],
+                subcommands: &[#(<#subcommand_types as serenity_commands::SubCommand>::METADATA),*],
+                default_member_permissions: None,
+                dm_permission: None,
+            };
+
             pub(crate) fn name() -> &'static str {
                 #group
             }
@@ -75,6 +143,48 @@ pub fn derive_group(item: TokenStream) -> Result<TokenStream> {
 
             #extra
         }
+
+        impl #impl_generics serenity_commands::Group for #name #ty_generics #where_clause {
+            const METADATA: &'static serenity_commands::info::CommandMeta = Self::METADATA;
+
+            fn name() -> &'static str {
+                Self::name()
+            }
+
+            #group_fn
+
+            fn parse_subcommand_group(
+                option: #interaction_option
+            ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+                Self::parse_subcommand_group(option)
+            }
+
+            fn to_subcommand_group_option(&self) -> #interaction_option {
+                self.to_subcommand_group_option()
+            }
+        }
+
+        impl #impl_generics serenity_commands::SubCommandOption for #name #ty_generics #where_clause {
+            const METADATA: &'static serenity_commands::info::CommandMeta = Self::METADATA;
+
+            fn name() -> &'static str {
+                Self::name()
+            }
+
+            #option_fn
+
+            fn parse_option(
+                option: #interaction_option
+            ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+                Self::parse_subcommand_group(option)
+            }
+
+            fn to_option(&self) -> #interaction_option {
+                self.to_subcommand_group_option()
+            }
+        }
+
+        #message_impl
     };
 
     Ok(output)
@@ -2,16 +2,27 @@ use proc_macro2::Ident;
 use syn::spanned::Spanned;
 use syn::*;
 
-use crate::common::{ensure_tuple_variant, get_lit_string, parse_doc, AttrOption};
+use crate::common::{
+    ensure_tuple_variant,
+    get_lit_string,
+    infer_name,
+    parse_doc,
+    variant_type,
+    AttrOption,
+    RenameRule,
+};
 
 pub struct Group {
     pub name: String,
     pub description: String,
-    pub subcommands: Vec<Ident>,
+    pub prefix: bool,
+    pub subcommands: Vec<(Ident, Type)>,
 }
 
 pub fn parse_group(input: &DeriveInput) -> Result<Group> {
     let mut name = AttrOption::new("name");
+    let mut prefix = AttrOption::new("prefix");
+    let mut rename_all = AttrOption::new("rename_all");
 
     let mut description = None;
 
@@ -42,6 +53,12 @@ pub fn parse_group(input: &DeriveInput) -> Result<Group> {
                 NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("name") => {
                     name.set(nv.span(), get_lit_string(&nv.lit)?)?;
                 },
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("rename_all") => {
+                    rename_all.set(nv.span(), RenameRule::from_lit(&nv.lit)?)?;
+                },
+                NestedMeta::Meta(Meta::Path(p)) if p.is_ident("prefix") => {
+                    prefix.set(p.span(), ())?;
+                },
                 _ => return Err(Error::new(meta.span(), "unknown option or invalid syntax")),
             };
         }
@@ -52,12 +69,7 @@ pub fn parse_group(input: &DeriveInput) -> Result<Group> {
         _ => return Err(Error::new(input.span(), "expected an enum")),
     };
 
-    let name = match name.value() {
-        Some(name) => name,
-        None => {
-            return Err(Error::new(input.ident.span(), "expected a name"));
-        },
-    };
+    let name = infer_name(name, rename_all.value(), &input.ident);
 
     let description = match description {
         Some(desc) => desc,
@@ -72,11 +84,12 @@ pub fn parse_group(input: &DeriveInput) -> Result<Group> {
     Ok(Group {
         name,
         description,
+        prefix: prefix.value().is_some(),
         subcommands,
     })
 }
 
-fn parse_enum(data: &DataEnum) -> Result<Vec<Ident>> {
+fn parse_enum(data: &DataEnum) -> Result<Vec<(Ident, Type)>> {
     if data.variants.len() > 25 {
         return Err(Error::new(
             data.variants.span(),
@@ -84,13 +97,13 @@ fn parse_enum(data: &DataEnum) -> Result<Vec<Ident>> {
         ));
     }
 
-    let mut idents = Vec::new();
+    let mut subcommands = Vec::new();
 
     for variant in &data.variants {
         ensure_tuple_variant(variant, "subcommand")?;
 
-        idents.push(variant.ident.clone());
+        subcommands.push((variant.ident.clone(), variant_type(variant)));
     }
 
-    Ok(idents)
+    Ok(subcommands)
 }
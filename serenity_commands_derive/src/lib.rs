@@ -2,23 +2,50 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 
+mod backend;
 mod common;
 
 mod command;
+mod command_fn;
 mod commands;
+mod component;
 mod group;
+mod modal;
+mod trace;
 
 #[proc_macro_derive(Command, attributes(command, option))]
 pub fn derive_command(item: TokenStream) -> TokenStream {
     command::derive_command(item.into()).unwrap_or_else(|e| e.into_compile_error()).into()
 }
 
-#[proc_macro_derive(Commands)]
+#[proc_macro_attribute]
+pub fn command(args: TokenStream, item: TokenStream) -> TokenStream {
+    command_fn::command_fn(args.into(), item.into())
+        .unwrap_or_else(|e| e.into_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(Commands, attributes(commands))]
 pub fn derive_commands(item: TokenStream) -> TokenStream {
     commands::derive_commands(item.into()).unwrap_or_else(|e| e.into_compile_error()).into()
 }
 
+#[proc_macro_derive(Component, attributes(component))]
+pub fn derive_component(item: TokenStream) -> TokenStream {
+    component::derive_component(item.into()).unwrap_or_else(|e| e.into_compile_error()).into()
+}
+
 #[proc_macro_derive(Group, attributes(group, option))]
 pub fn derive_group(item: TokenStream) -> TokenStream {
     group::derive_group(item.into()).unwrap_or_else(|e| e.into_compile_error()).into()
 }
+
+#[proc_macro_derive(Modal, attributes(modal, input))]
+pub fn derive_modal(item: TokenStream) -> TokenStream {
+    modal::derive_modal(item.into()).unwrap_or_else(|e| e.into_compile_error()).into()
+}
+
+#[proc_macro_derive(Options, attributes(options, option))]
+pub fn derive_options(item: TokenStream) -> TokenStream {
+    command::derive_options(item.into()).unwrap_or_else(|e| e.into_compile_error()).into()
+}
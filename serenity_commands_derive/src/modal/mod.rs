@@ -0,0 +1,172 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::*;
+
+mod parse;
+
+use parse::*;
+
+use crate::backend::{self, Backend};
+
+pub fn derive_modal(item: TokenStream) -> Result<TokenStream> {
+    let input = parse2::<DeriveInput>(item)?;
+
+    backend::require_serenity(input.ident.span(), "`#[derive(Modal)]`")?;
+
+    let Modal {
+        id,
+        title,
+        inputs,
+    } = parse_modal(&input)?;
+
+    let name = input.ident;
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let text_inputs = inputs.iter().map(generate_text_input);
+    let parsing = inputs.iter().map(generate_input_parsing);
+    let idents = inputs.iter().map(|i| &i.ident);
+
+    let response = quote!(serenity_commands::serenity::builder::CreateInteractionResponse);
+
+    let signature = match Backend::get() {
+        Backend::Serenity0_10 => quote! {
+            create_modal(response: &mut #response) -> &mut #response
+        },
+        Backend::Serenity0_11 => quote! {
+            create_modal<'a, 'b>(response: &'a mut #response<'b>) -> &'a mut #response<'b>
+        },
+        Backend::Serenity0_12 | Backend::Twilight => quote!(create_modal() -> #response),
+    };
+
+    let response_arg =
+        if backend::value_builders() { TokenStream::new() } else { quote!(response,) };
+
+    // Serenity 0.10 has no modal submit interactions, whose submissions are
+    // parsed out of the raw payload.
+    let interaction = match Backend::get() {
+        Backend::Serenity0_11 => Some(quote!(
+            serenity_commands::serenity::model::interactions::modal::ModalSubmitInteraction
+        )),
+        Backend::Serenity0_12 => {
+            Some(quote!(serenity_commands::serenity::model::application::ModalInteraction))
+        },
+        Backend::Serenity0_10 | Backend::Twilight => None,
+    };
+
+    let parse_interaction = interaction.map(|interaction| {
+        quote! {
+            pub(crate) fn parse(
+                interaction: &#interaction
+            ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+                Self::parse_submit(serenity_commands::modal::ModalSubmit::from_interaction(interaction))
+            }
+        }
+    });
+
+    let output = quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            pub(crate) fn custom_id() -> &'static str {
+                #id
+            }
+
+            pub(crate) fn title() -> &'static str {
+                #title
+            }
+
+            pub(crate) fn #signature {
+                #[allow(unused_imports)]
+                use serenity_commands::modal::{TextInput, TextInputStyle};
+
+                serenity_commands::modal::create_modal(
+                    #response_arg
+                    Self::custom_id(),
+                    Self::title(),
+                    &[#(#text_inputs),*],
+                )
+            }
+
+            #parse_interaction
+
+            #[allow(unused_mut)]
+            pub(crate) fn parse_submit(
+                mut submit: serenity_commands::modal::ModalSubmit
+            ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+                if submit.custom_id != Self::custom_id() {
+                    return Err(serenity_commands::error::ParseError::UnknownModal(submit.custom_id));
+                }
+
+                #(#parsing)*
+
+                Ok(Self {
+                    #(#idents),*
+                })
+            }
+
+            pub(crate) fn parse_json(
+                interaction: &serenity_commands::serde_json::Value
+            ) -> std::result::Result<Self, serenity_commands::error::ParseError> {
+                Self::parse_submit(serenity_commands::modal::ModalSubmit::from_json(interaction)?)
+            }
+        }
+    };
+
+    Ok(output)
+}
+
+fn quote_option<T: quote::ToTokens>(value: &Option<T>) -> TokenStream {
+    match value {
+        Some(value) => quote!(Some(#value)),
+        None => quote!(None),
+    }
+}
+
+fn generate_text_input(input: &Input) -> TokenStream {
+    let Input {
+        name,
+        label,
+        style,
+        required,
+        ..
+    } = input;
+
+    let min_len = quote_option(&input.min_len);
+    let max_len = quote_option(&input.max_len);
+    let placeholder = quote_option(&input.placeholder);
+
+    quote! {
+        TextInput {
+            custom_id: #name,
+            label: #label,
+            style: TextInputStyle::#style,
+            required: #required,
+            min_len: #min_len,
+            max_len: #max_len,
+            placeholder: #placeholder,
+        }
+    }
+}
+
+fn generate_input_parsing(input: &Input) -> TokenStream {
+    let ident = &input.ident;
+    let name = &input.name;
+
+    let invalid = quote!(|_| serenity_commands::error::ParseError::InvalidInput(#name));
+
+    if input.required {
+        quote! {
+            let #ident = submit
+                .take(#name)
+                .ok_or(serenity_commands::error::ParseError::MissingInput(#name))?
+                .parse()
+                .map_err(#invalid)?;
+        }
+    } else {
+        quote! {
+            let #ident = match submit.take(#name) {
+                Some(value) => Some(value.parse().map_err(#invalid)?),
+                None => None,
+            };
+        }
+    }
+}
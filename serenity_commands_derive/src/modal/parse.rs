@@ -0,0 +1,269 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::spanned::Spanned;
+use syn::*;
+
+use crate::common::{
+    get_lit_int,
+    get_lit_string,
+    get_path_as_string,
+    is_option,
+    parse_doc,
+    AttrOption,
+};
+
+pub struct Modal {
+    pub id: String,
+    pub title: String,
+    pub inputs: Vec<Input>,
+}
+
+pub fn parse_modal(input: &DeriveInput) -> Result<Modal> {
+    let mut id = AttrOption::new("id");
+
+    let mut title = None;
+
+    for attr in &input.attrs {
+        if attr.path.is_ident("doc") {
+            if title.is_some() {
+                return Err(Error::new(
+                    attr.span(),
+                    "documentation string has already been provided",
+                ));
+            }
+
+            title = Some(parse_doc(attr)?);
+            continue;
+        }
+
+        if !attr.path.is_ident("modal") {
+            continue;
+        }
+
+        let list = match attr.parse_meta()? {
+            Meta::List(l) => l,
+            _ => return Err(Error::new(attr.span(), "expected a list")),
+        };
+
+        for meta in list.nested {
+            match meta {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("id") => {
+                    id.set(nv.span(), get_lit_string(&nv.lit)?)?;
+                },
+                _ => return Err(Error::new(meta.span(), "unknown option or invalid syntax")),
+            };
+        }
+    }
+
+    let id = match id.value() {
+        Some(id) => id,
+        None => return Err(Error::new(input.ident.span(), "expected an id")),
+    };
+
+    let title = match title {
+        Some(title) => title,
+        None => {
+            return Err(Error::new(input.ident.span(), "expected a title in documentation string"));
+        },
+    };
+
+    if title.chars().count() > 45 {
+        return Err(Error::new(
+            input.ident.span(),
+            "a modal title cannot be longer than 45 characters",
+        ));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(n), ..
+        }) => &n.named,
+        _ => return Err(Error::new(input.span(), "expected a struct with named fields")),
+    };
+
+    if fields.len() > 5 {
+        return Err(Error::new(fields.span(), "a modal cannot have more than 5 inputs"));
+    }
+
+    let mut inputs = Vec::new();
+    for field in fields {
+        inputs.push(Input::new(field)?);
+    }
+
+    Ok(Modal {
+        id,
+        title,
+        inputs,
+    })
+}
+
+#[derive(Clone, Copy)]
+pub enum InputStyle {
+    Short,
+    Paragraph,
+}
+
+impl InputStyle {
+    fn new(s: &str) -> Option<Self> {
+        Some(match s {
+            "short" => Self::Short,
+            "paragraph" => Self::Paragraph,
+            _ => return None,
+        })
+    }
+}
+
+impl ToTokens for InputStyle {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            Self::Short => quote!(Short),
+            Self::Paragraph => quote!(Paragraph),
+        });
+    }
+}
+
+pub struct Input {
+    pub ident: Ident,
+    pub required: bool,
+    pub name: String,
+    pub label: String,
+    pub style: InputStyle,
+    pub min_len: Option<u16>,
+    pub max_len: Option<u16>,
+    pub placeholder: Option<String>,
+}
+
+impl Input {
+    fn new(field: &Field) -> Result<Self> {
+        let ident = field.ident.clone().expect("named field");
+
+        let mut name = AttrOption::new("name");
+        let mut min_len = AttrOption::new("min_len");
+        let mut max_len = AttrOption::new("max_len");
+        let mut placeholder = AttrOption::new("placeholder");
+
+        let mut label = None;
+        let mut style = None;
+
+        for attr in &field.attrs {
+            if attr.path.is_ident("doc") {
+                if label.is_some() {
+                    return Err(Error::new(
+                        attr.span(),
+                        "documentation string has already been provided",
+                    ));
+                }
+
+                label = Some(parse_doc(attr)?);
+                continue;
+            }
+
+            if !attr.path.is_ident("input") {
+                continue;
+            }
+
+            let list = match attr.parse_meta()? {
+                Meta::List(l) => l,
+                _ => return Err(Error::new(attr.span(), "expected a list")),
+            };
+
+            for meta in list.nested {
+                match &meta {
+                    NestedMeta::Lit(_) => {
+                        return Err(Error::new(meta.span(), "unexpected literal"));
+                    },
+                    NestedMeta::Meta(m) => match m {
+                        // `name = "..."` option
+                        Meta::NameValue(nv) if nv.path.is_ident("name") => {
+                            name.set(nv.span(), get_lit_string(&nv.lit)?)?;
+                        },
+
+                        // `min_len = ...` option
+                        Meta::NameValue(nv) if nv.path.is_ident("min_len") => {
+                            min_len.set(nv.span(), parse_length(&nv.lit)?)?;
+                        },
+
+                        // `max_len = ...` option
+                        Meta::NameValue(nv) if nv.path.is_ident("max_len") => {
+                            max_len.set(nv.span(), parse_length(&nv.lit)?)?;
+                        },
+
+                        // `placeholder = "..."` option
+                        Meta::NameValue(nv) if nv.path.is_ident("placeholder") => {
+                            placeholder.set(nv.span(), get_lit_string(&nv.lit)?)?;
+                        },
+
+                        // `short` | `paragraph` option
+                        Meta::Path(p) => {
+                            if style.is_some() {
+                                return Err(Error::new(
+                                    p.span(),
+                                    "input style has already been provided",
+                                ));
+                            }
+
+                            style =
+                                match InputStyle::new(&get_path_as_string(p)?) {
+                                    Some(style) => Some(style),
+                                    None => return Err(Error::new(
+                                        p.span(),
+                                        "expected an input style (either `short` or `paragraph`)",
+                                    )),
+                                };
+                        },
+                        _ => {
+                            return Err(Error::new(meta.span(), "unknown option or invalid syntax"))
+                        },
+                    },
+                };
+            }
+        }
+
+        let label = match label {
+            Some(label) => label,
+            None => {
+                return Err(Error::new(
+                    field.span(),
+                    "expected a documentation string for the label",
+                ))
+            },
+        };
+
+        if label.chars().count() > 45 {
+            return Err(Error::new(
+                field.span(),
+                "an input label cannot be longer than 45 characters",
+            ));
+        }
+
+        let min_len = min_len.value();
+        let max_len = max_len.value();
+
+        if let (Some(min), Some(max)) = (min_len, max_len) {
+            if min > max {
+                return Err(Error::new(field.span(), "`min_len` cannot be greater than `max_len`"));
+            }
+        }
+
+        Ok(Self {
+            name: name.value().unwrap_or_else(|| ident.to_string()),
+            ident,
+            required: !is_option(&field.ty),
+            label,
+            style: style.unwrap_or(InputStyle::Short),
+            min_len,
+            max_len,
+            placeholder: placeholder.value(),
+        })
+    }
+}
+
+fn parse_length(lit: &Lit) -> Result<u16> {
+    let len = get_lit_int(lit)?;
+
+    if len > 4000 {
+        return Err(Error::new(lit.span(), "an input cannot be longer than 4000 characters"));
+    }
+
+    Ok(len)
+}
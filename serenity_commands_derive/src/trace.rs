@@ -0,0 +1,25 @@
+//! Instrumentation of the generated code, emitted with the `tracing` feature
+//! through the functions of `serenity_commands::trace`.
+
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+
+/// Wraps `parse`, which parses the interaction bound to `interaction`, in a
+/// span.
+pub fn parse(interaction: &Ident, parse: TokenStream) -> TokenStream {
+    if cfg!(feature = "tracing") {
+        quote!(serenity_commands::trace::parse(#interaction, |#interaction| #parse))
+    } else {
+        parse
+    }
+}
+
+/// Wraps `registration`, a future registering commands globally or in the
+/// guild with the id `guild_id` evaluates to, in a span.
+pub fn register(guild_id: TokenStream, registration: TokenStream) -> TokenStream {
+    if cfg!(feature = "tracing") {
+        quote!(serenity_commands::trace::register(#guild_id, #registration))
+    } else {
+        registration
+    }
+}